@@ -1,43 +1,14 @@
 use anyhow::{Context, Result};
 use serde_json::json;
-use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager};
 use tauri_plugin_store::StoreExt;
 
-use crate::rustustc::cas::client::CASClient;
 use crate::rustustc::young::model::User;
-use crate::rustustc::young::YouthService;
 use crate::security::{decrypt_data, encrypt_data};
 use crate::state::AppState;
 
 const CREDENTIALS_STORE: &str = "credentials.json";
 
-/// 执行核心登录逻辑：CAS Login -> Youth Service Init -> Update State
-pub async fn perform_login(
-    state: &State<'_, AppState>,
-    username: &str,
-    password: &str,
-) -> Result<User> {
-    // 1. CAS 登录
-    let client = CASClient::new();
-    client.login_by_pwd(Some(username), Some(password)).await?;
-
-    // 2. Youth Service 初始化
-    let youth = YouthService::new(&client).await?;
-
-    let client_arc = Arc::new(client);
-    let youth_arc = Arc::new(youth);
-
-    // 3. 获取用户信息 (验证 Token 有效性)
-    let user_info = User::get_current(&youth_arc).await?;
-
-    // 4. 更新全局状态
-    *state.cas_client.lock().await = Some(client_arc);
-    *state.youth_service.lock().await = Some(youth_arc);
-
-    Ok(user_info)
-}
-
 /// 保存凭据 (加密)
 pub fn save_credentials(app: &AppHandle, username: &str, password: &str) -> Result<()> {
     let store = app
@@ -53,19 +24,47 @@ pub fn save_credentials(app: &AppHandle, username: &str, password: &str) -> Resu
     Ok(())
 }
 
-/// 尝试自动登录
-/// 返回: (是否登录成功, 是否有存储的账号, 用户名, 用户信息/错误信息)
-pub async fn try_auto_login(
-    app: &AppHandle,
-    state: &State<'_, AppState>,
-) -> Result<(bool, bool, Option<String>, Option<User>)> {
+/// 存储的用户名与是否保存了密码。
+pub fn stored_account(app: &AppHandle) -> Result<(Option<String>, bool)> {
+    let store = app
+        .store(CREDENTIALS_STORE)
+        .context("Failed to access store")?;
+    let username = store
+        .get("username")
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+    Ok((username, store.get("password").is_some()))
+}
+
+/// 本地数据（报名信息、保存的搜索、签到记录等）所属的账号。
+/// 旧版本没有记录时视为存储的用户名。
+pub fn data_owner(app: &AppHandle) -> Result<Option<String>> {
+    let store = app
+        .store(CREDENTIALS_STORE)
+        .context("Failed to access store")?;
+    Ok(store
+        .get("data_owner")
+        .or_else(|| store.get("username"))
+        .and_then(|v| v.as_str().map(|s| s.to_string())))
+}
+
+pub fn set_data_owner(app: &AppHandle, username: &str) -> Result<()> {
+    let store = app
+        .store(CREDENTIALS_STORE)
+        .context("Failed to access store")?;
+    store.set("data_owner", json!(username));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 尝试自动登录：内存中的会话仍有效时直接使用，否则解密存储的凭据重新登录。
+/// 返回: (是否登录成功, 是否有存储的账号, 用户名, 用户信息)
+pub async fn try_auto_login(app: &AppHandle) -> Result<(bool, bool, Option<String>, Option<User>)> {
+    let session = &app.state::<AppState>().session;
+
     // 1. 检查内存状态
-    {
-        let youth_guard = state.youth_service.lock().await;
-        if let Some(youth) = &*youth_guard {
-            if let Ok(info) = User::get_current(youth).await {
-                return Ok((true, true, Some(info.id.clone()), Some(info)));
-            }
+    if let Some(s) = session.session() {
+        if let Ok(info) = User::get_current(&s.youth).await {
+            return Ok((true, true, Some(s.username.clone()), Some(info)));
         }
     }
 
@@ -83,7 +82,7 @@ pub async fn try_auto_login(
 
     if let (Some(ref username), Some(ref encrypted_pwd)) = (&username_opt, &encrypted_pwd_opt) {
         // 解密密码
-        let password = match decrypt_data(app, &encrypted_pwd) {
+        let password = match decrypt_data(app, encrypted_pwd) {
             Ok(p) => p,
             Err(e) => {
                 // 解密失败（可能换了机器或文件损坏），视为无凭据，或者返回错误
@@ -92,8 +91,8 @@ pub async fn try_auto_login(
             }
         };
 
-        // 尝试登录
-        match perform_login(state, username, &password).await {
+        // 尝试登录（与其他登录请求串行，不会重复登录）
+        match session.login(app, username, &password).await {
             Ok(user) => Ok((true, true, Some(username.clone()), Some(user))),
             Err(_) => Ok((false, true, Some(username.clone()), None)), // 登录失败但账号存在
         }
@@ -108,6 +107,16 @@ pub async fn try_auto_login(
     }
 }
 
+/// 忘记账号：删除用户名、密码与数据所属账号
+pub fn forget_account(app: &AppHandle) -> Result<()> {
+    let store = app
+        .store(CREDENTIALS_STORE)
+        .context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
 /// 登出并清除凭据
 pub fn clear_credentials(app: &AppHandle) -> Result<()> {
     let store = app
@@ -0,0 +1,172 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::profile;
+use crate::rustustc::young::batch::{self, BatchItem, BatchOutcome};
+use crate::rustustc::young::model::{now, SignInfo, TimePeriod};
+use crate::rustustc::young::{SecondClass, YouthService};
+
+/// 每处理完一个活动发送一次。
+pub const PROGRESS_EVENT: &str = "batch://progress";
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchKind {
+    Register,
+    Cancel,
+}
+
+#[derive(Clone, Serialize)]
+struct Progress<'a> {
+    kind: BatchKind,
+    done: usize,
+    total: usize,
+    item: &'a BatchItem,
+}
+
+fn report(
+    app: &AppHandle,
+    kind: BatchKind,
+    total: usize,
+    items: &mut Vec<BatchItem>,
+    item: BatchItem,
+) {
+    let _ = app.emit(
+        PROGRESS_EVENT,
+        Progress {
+            kind,
+            done: items.len() + 1,
+            total,
+            item: &item,
+        },
+    );
+    items.push(item);
+}
+
+/// 按顺序报名多个活动。已报名列表与报名信息只获取一次，请求经全局限流。
+pub async fn register(
+    app: &AppHandle,
+    service: &YouthService,
+    ids: &[String],
+    profile_id: Option<&str>,
+) -> Result<Vec<BatchItem>> {
+    let now = now();
+    let mut taken: Vec<(String, TimePeriod)> = SecondClass::get_participated(service)
+        .await?
+        .iter()
+        .filter(|sc| crate::is_still_registered(sc, now))
+        .filter_map(|sc| Some((sc.id.clone(), sc.hold_time().ok()?)))
+        .collect();
+
+    let base_info = match profile::get_profile(app, profile_id)? {
+        Some(p) => {
+            if let Some(e) = p.info.validate().first() {
+                bail!("Invalid sign info: {}: {}", e.field, e.message);
+            }
+            Some(p.info)
+        }
+        None => None,
+    };
+    let mut self_info: Option<SignInfo> = None;
+
+    let mut items = Vec::with_capacity(ids.len());
+    for id in ids {
+        let sc = match SecondClass::get_by_id(service, id).await {
+            Ok(sc) => sc,
+            Err(e) => {
+                let item = BatchItem {
+                    activity_id: id.clone(),
+                    activity_name: String::new(),
+                    outcome: BatchOutcome::Error {
+                        message: e.to_string(),
+                    },
+                };
+                report(app, BatchKind::Register, ids.len(), &mut items, item);
+                continue;
+            }
+        };
+
+        let outcome = match batch::precheck(&sc, &taken) {
+            Some(outcome) => outcome,
+            None => {
+                let sign_info = if sc.need_sign_info() {
+                    match &base_info {
+                        Some(info) => {
+                            let mut info = info.clone();
+                            if let Ok(Some(r)) = profile::get_activity_remarks(app, id) {
+                                info.remarks = r;
+                            }
+                            Some(info)
+                        }
+                        None => {
+                            if self_info.is_none() {
+                                self_info = SignInfo::get_self(service).await.ok();
+                            }
+                            self_info.clone()
+                        }
+                    }
+                } else {
+                    None
+                };
+                match sc.apply(service, true, false, sign_info, |_| None).await {
+                    Ok(_) => BatchOutcome::Success,
+                    Err(e) => batch::classify_error(&e.to_string()),
+                }
+            }
+        };
+        if outcome == BatchOutcome::Success {
+            if let Ok(ht) = sc.hold_time() {
+                taken.push((sc.id.clone(), ht));
+            }
+        }
+        let item = BatchItem {
+            activity_id: sc.id.clone(),
+            activity_name: sc.name.clone(),
+            outcome,
+        };
+        report(app, BatchKind::Register, ids.len(), &mut items, item);
+    }
+    Ok(items)
+}
+
+/// 取消多个活动的报名。只取消已报名且尚未开始的活动。
+pub async fn cancel(
+    app: &AppHandle,
+    service: &YouthService,
+    ids: &[String],
+) -> Result<Vec<BatchItem>> {
+    let now = now();
+    let participated = SecondClass::get_participated(service).await?;
+
+    let mut items = Vec::with_capacity(ids.len());
+    for id in ids {
+        let Some(sc) = participated.iter().find(|sc| sc.id == *id) else {
+            let item = BatchItem {
+                activity_id: id.clone(),
+                activity_name: String::new(),
+                outcome: BatchOutcome::Error {
+                    message: "Not registered for this activity".into(),
+                },
+            };
+            report(app, BatchKind::Cancel, ids.len(), &mut items, item);
+            continue;
+        };
+        let started = sc.hold_time().is_ok_and(|ht| ht.start <= now);
+        let outcome = if started {
+            BatchOutcome::Closed
+        } else {
+            match sc.cancel_apply(service).await {
+                Ok(_) => BatchOutcome::Success,
+                Err(e) => batch::classify_error(&e.to_string()),
+            }
+        };
+        let item = BatchItem {
+            activity_id: sc.id.clone(),
+            activity_name: sc.name.clone(),
+            outcome,
+        };
+        report(app, BatchKind::Cancel, ids.len(), &mut items, item);
+    }
+    Ok(items)
+}
@@ -0,0 +1,53 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::rustustc::young::model::{cst_serde, CstDateTime};
+use crate::rustustc::young::CheckKind;
+
+const CHECKIN_STORE: &str = "checkin_history.json";
+/// 最多保留的记录数
+const MAX_ENTRIES: usize = 200;
+
+/// 一次扫码签到 / 签退的结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckinRecord {
+    pub activity_id: String,
+    pub activity_name: String,
+    pub kind: CheckKind,
+    #[serde(with = "cst_serde")]
+    pub time: CstDateTime,
+    pub success: bool,
+    /// 失败原因
+    pub message: Option<String>,
+}
+
+/// 最近的记录在前。
+pub fn list(app: &AppHandle) -> Result<Vec<CheckinRecord>> {
+    let store = app.store(CHECKIN_STORE).context("Failed to access store")?;
+    Ok(store
+        .get("records")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+pub fn record(app: &AppHandle, entry: CheckinRecord) -> Result<()> {
+    let mut records = list(app)?;
+    records.insert(0, entry);
+    records.truncate(MAX_ENTRIES);
+
+    let store = app.store(CHECKIN_STORE).context("Failed to access store")?;
+    store.set("records", json!(records));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let store = app.store(CHECKIN_STORE).context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
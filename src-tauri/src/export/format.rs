@@ -0,0 +1,218 @@
+use crate::rustustc::young::model::{CstDateTime, SecondClass};
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+// ==================== 导出格式 ====================
+//
+// 纯字符串生成，不涉及文件读写：CSV / JSON 成绩单与 RFC 5545 日历。
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Ics,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+/// 成绩单中的一行。时间为 `%Y-%m-%d %H:%M`，无法解析时为空。
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptRow {
+    pub id: String,
+    pub name: String,
+    pub module: String,
+    pub department: String,
+    pub start: String,
+    pub end: String,
+    pub hours: Option<f64>,
+    pub status: String,
+}
+
+impl TranscriptRow {
+    pub fn from_activity(sc: &SecondClass) -> Self {
+        let hold = sc.hold_time().ok();
+        let fmt = |t: CstDateTime| t.format("%Y-%m-%d %H:%M").to_string();
+        Self {
+            id: sc.id.clone(),
+            name: sc.name.clone(),
+            module: sc.module().map(|m| m.text).unwrap_or_default(),
+            department: sc.department().map(|d| d.name).unwrap_or_default(),
+            start: hold.map(|t| fmt(t.start)).unwrap_or_default(),
+            end: hold.map(|t| fmt(t.end)).unwrap_or_default(),
+            hours: sc.hours(),
+            status: sc.status().text().to_string(),
+        }
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// CSV 成绩单，带 UTF-8 BOM 以便 Excel 正确识别中文。
+pub fn to_csv(activities: &[SecondClass]) -> String {
+    let mut out = String::from("\u{feff}活动名称,模块,组织单位,开始时间,结束时间,学时,状态\r\n");
+    for row in activities.iter().map(TranscriptRow::from_activity) {
+        let hours = row.hours.map(|h| h.to_string()).unwrap_or_default();
+        let fields = [
+            &row.name,
+            &row.module,
+            &row.department,
+            &row.start,
+            &row.end,
+            &hours,
+            &row.status,
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+pub fn to_json(activities: &[SecondClass]) -> Result<String> {
+    let rows: Vec<TranscriptRow> = activities
+        .iter()
+        .map(TranscriptRow::from_activity)
+        .collect();
+    Ok(serde_json::to_string_pretty(&rows)?)
+}
+
+// ==================== iCalendar ====================
+
+const ICS_TZID: &str = "Asia/Shanghai";
+
+/// TEXT 值转义（RFC 5545 3.3.11）。
+fn ics_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 参数值含 `:;,` 时需要加引号，且不能包含引号本身。
+fn ics_param(s: &str) -> String {
+    let s = s.replace('"', "'");
+    if s.contains([':', ';', ',']) {
+        format!("\"{}\"", s)
+    } else {
+        s
+    }
+}
+
+/// 按 75 字节折行（不拆开 UTF-8 字符），行尾 CRLF。
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+/// 由活动生成日历；每个 VEVENT 按 `reminders`（开始前分钟数）附带 VALARM。
+/// 无法解析举办时间的活动会被跳过。
+pub fn to_ics(activities: &[SecondClass], reminders: &[u32]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let local = |t: CstDateTime| t.format("%Y%m%dT%H%M%S").to_string();
+
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//BetterUSTC//Second Class//ZH",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        "X-WR-CALNAME:第二课堂",
+        "BEGIN:VTIMEZONE",
+        "TZID:Asia/Shanghai",
+        "BEGIN:STANDARD",
+        "DTSTART:19700101T000000",
+        "TZOFFSETFROM:+0800",
+        "TZOFFSETTO:+0800",
+        "TZNAME:CST",
+        "END:STANDARD",
+        "END:VTIMEZONE",
+    ] {
+        push_line(&mut out, line);
+    }
+
+    for sc in activities {
+        let Ok(hold) = sc.hold_time() else {
+            continue;
+        };
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}@young.ustc.edu.cn", sc.id));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(
+            &mut out,
+            &format!("DTSTART;TZID={}:{}", ICS_TZID, local(hold.start)),
+        );
+        push_line(
+            &mut out,
+            &format!("DTEND;TZID={}:{}", ICS_TZID, local(hold.end)),
+        );
+        push_line(&mut out, &format!("SUMMARY:{}", ics_text(&sc.name)));
+        if let Some(place) = sc.place() {
+            push_line(&mut out, &format!("LOCATION:{}", ics_text(&place)));
+        }
+        let mut description = Vec::new();
+        if let Some(module) = sc.module() {
+            description.push(format!("模块：{}", module.text));
+        }
+        if let Some(hours) = sc.hours() {
+            description.push(format!("学时：{}", hours));
+        }
+        if let Some(organizer) = sc.organizer() {
+            // 二课接口不提供主办方邮箱，按惯例使用 invalid:nomail
+            push_line(
+                &mut out,
+                &format!("ORGANIZER;CN={}:invalid:nomail", ics_param(&organizer)),
+            );
+            description.push(format!("主办方：{}", organizer));
+        }
+        if !description.is_empty() {
+            push_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", ics_text(&description.join("\n"))),
+            );
+        }
+        for minutes in reminders {
+            push_line(&mut out, "BEGIN:VALARM");
+            push_line(&mut out, "ACTION:DISPLAY");
+            push_line(&mut out, &format!("TRIGGER:-PT{}M", minutes));
+            push_line(&mut out, &format!("DESCRIPTION:{}", ics_text(&sc.name)));
+            push_line(&mut out, "END:VALARM");
+        }
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
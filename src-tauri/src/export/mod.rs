@@ -0,0 +1,49 @@
+pub mod format;
+
+pub use format::{to_csv, to_ics, to_json, ExportFormat, TranscriptRow};
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_fs::{FilePath, FsExt, OpenOptions};
+use tokio::sync::oneshot;
+
+/// 写出导出内容。`path` 为空时弹出保存对话框，用户取消返回 `None`。
+/// 对话框以回调方式打开，等待期间不占用异步运行时的工作线程。
+pub async fn write_export(
+    app: &AppHandle,
+    content: &str,
+    format: ExportFormat,
+    default_name: &str,
+    path: Option<String>,
+) -> Result<Option<String>> {
+    let target = match path {
+        // 支持普通路径与移动端的 content:// URI
+        Some(p) => p.parse::<FilePath>().unwrap_or_else(|e| match e {}),
+        None => {
+            let (tx, rx) = oneshot::channel();
+            app.dialog()
+                .file()
+                .set_file_name(format!("{}.{}", default_name, format.extension()))
+                .add_filter(format.extension().to_uppercase(), &[format.extension()])
+                .save_file(move |p| {
+                    let _ = tx.send(p);
+                });
+            match rx.await.ok().flatten() {
+                Some(p) => p,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    let mut file = app
+        .fs()
+        .open(target.clone(), options)
+        .context("Failed to open export file")?;
+    file.write_all(content.as_bytes())
+        .context("Failed to write export file")?;
+    Ok(Some(target.to_string()))
+}
@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::favorite::{should_queue, transient, FavoriteCache};
+use crate::rustustc::young::{SecondClass, YouthService};
+
+const FAVORITES_STORE: &str = "favorites.json";
+
+/// 串行化对 `favorites.json` 的读改写（收藏、列表与同步命令可能并发执行）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 读取本地收藏缓存；读取失败时视为空。
+pub fn load(app: &AppHandle) -> FavoriteCache {
+    app.store(FAVORITES_STORE)
+        .ok()
+        .and_then(|store| store.get("data"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, cache: &FavoriteCache) -> Result<()> {
+    let store = app
+        .store(FAVORITES_STORE)
+        .context("Failed to access store")?;
+    store.set("data", json!(cache));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 在写锁内读取收藏缓存、修改并写回；网络请求应在调用前完成。
+fn modify<T>(app: &AppHandle, f: impl FnOnce(&mut FavoriteCache) -> T) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut cache = load(app);
+    let out = f(&mut cache);
+    save(app, &cache)?;
+    Ok(out)
+}
+
+async fn send(service: &YouthService, sc: &SecondClass, favorite: bool) -> Result<(), AppError> {
+    let res = if favorite {
+        sc.favorite(service).await
+    } else {
+        sc.unfavorite(service).await
+    };
+    res.map(|_| ()).map_err(AppError::from)
+}
+
+/// 收藏或取消收藏；网络问题、会话失效或收藏接口不可用时记入本地待同步队列，规则类错误直接返回。
+/// 返回是否已同步到服务器。
+pub async fn set_favorite(
+    app: &AppHandle,
+    service: &YouthService,
+    activity_id: &str,
+    favorite: bool,
+) -> Result<bool> {
+    let sc = match SecondClass::get_by_id(service, activity_id)
+        .await
+        .map_err(AppError::from)
+    {
+        Ok(sc) => sc,
+        // 拿不到活动详情时只记录操作，不缓存占位快照；详情接口的业务拒绝直接返回
+        Err(e) if transient(&e) => {
+            modify(app, |cache| cache.record_id(activity_id, favorite))?;
+            return Ok(false);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    match send(service, &sc, favorite).await {
+        Ok(()) => {
+            modify(app, |cache| cache.confirm(&sc, favorite))?;
+            Ok(true)
+        }
+        Err(e) if should_queue(&e) => {
+            modify(app, |cache| cache.record(&sc, favorite))?;
+            Ok(false)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 按顺序补发未同步的操作，再用服务器列表刷新快照。
+/// 规则类错误的操作直接丢弃；网络问题、会话失效或接口不可用时停止，剩余操作留待下次。
+/// 服务器列表不可用时保留本地快照。
+pub async fn sync(app: &AppHandle, service: &YouthService) -> Result<FavoriteCache> {
+    let mut done = Vec::new();
+    for op in load(app).pending {
+        match send(service, &SecondClass::from_id(&op.id), op.favorite).await {
+            Err(e) if should_queue(&e) => break,
+            _ => done.push(op),
+        }
+    }
+    let remote = SecondClass::list_favorites(service).await.ok();
+    // 补发期间可能有新的操作入队：只移除已发出的那一条，同一活动之后的操作保留
+    modify(app, |cache| {
+        cache.pending.retain(|op| !done.contains(op));
+        if let Some(remote) = remote {
+            cache.replace_remote(remote);
+        }
+        cache.clone()
+    })
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app
+        .store(FAVORITES_STORE)
+        .context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::auth;
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::keepalive::{decide, Health, KeepAliveAction, KeepAliveSettings};
+use crate::rustustc::young::model::{now, User};
+use crate::session::{Session, SessionState};
+use crate::settings;
+use crate::state::AppState;
+
+pub const SETTINGS_KEY: &str = "keep_alive";
+/// 检查是否到期的间隔；按墙上时间计算，系统休眠后也能及时检查
+const TICK: Duration = Duration::from_secs(30);
+
+/// 同一时间只进行一次检查（后台任务与前端回到前台时的检查可能重叠）
+static CHECKING: Mutex<()> = Mutex::const_new(());
+
+async fn probe(session: &Session) -> (Health, Health) {
+    let cas = match session.cas.check_login().await {
+        Ok(true) => Health::Valid,
+        Ok(false) => Health::Expired,
+        Err(_) => Health::Offline,
+    };
+    let youth = match User::get_current(&session.youth)
+        .await
+        .map_err(AppError::from)
+    {
+        Ok(_) => Health::Valid,
+        Err(e) if e.retryable() => Health::Offline,
+        Err(_) => Health::Expired,
+    };
+    (cas, youth)
+}
+
+/// 最后手段：用存储的凭据重新登录。没有保存密码或登录失败时为 `Backoff`。
+async fn relogin(app: &AppHandle) -> KeepAliveAction {
+    match auth::try_auto_login(app).await {
+        Ok((true, ..)) => KeepAliveAction::Relogin,
+        _ => KeepAliveAction::Backoff,
+    }
+}
+
+/// 刷新 token；CAS 已失效时转为重新登录。
+async fn refresh(app: &AppHandle) -> KeepAliveAction {
+    match app.state::<AppState>().session.refresh(app).await {
+        Ok(_) => KeepAliveAction::Refresh,
+        Err(e) if e.retryable() => KeepAliveAction::Backoff,
+        Err(_) => relogin(app).await,
+    }
+}
+
+/// 检查一次会话并按需刷新或重新登录，返回执行的处理；`Backoff` 表示离线或未能恢复。
+/// 未登录、登录中或已有检查在进行时直接返回 `Healthy`。
+pub async fn check(app: &AppHandle, settings: &KeepAliveSettings) -> KeepAliveAction {
+    let Ok(_checking) = CHECKING.try_lock() else {
+        return KeepAliveAction::Healthy;
+    };
+    let manager = &app.state::<AppState>().session;
+    match manager.state() {
+        SessionState::Active { .. } => {}
+        SessionState::Expired { .. } => return refresh(app).await,
+        _ => return KeepAliveAction::Healthy,
+    }
+    let Some(session) = manager.session() else {
+        return KeepAliveAction::Healthy;
+    };
+
+    let (cas, youth) = probe(&session).await;
+    let token_age = (now() - session.token_since).to_std().unwrap_or_default();
+    match decide(cas, youth, token_age, settings) {
+        KeepAliveAction::Refresh => refresh(app).await,
+        KeepAliveAction::Relogin => {
+            let (_, has_password) = auth::stored_account(app).unwrap_or_default();
+            // 没有保存密码时，token 仍有效就继续使用，失效后再提示重新登录
+            if has_password || youth != Health::Valid {
+                manager.mark_expired(app, "CAS session expired. Please login again.");
+            }
+            if has_password {
+                relogin(app).await
+            } else {
+                KeepAliveAction::Healthy
+            }
+        }
+        action => action,
+    }
+}
+
+/// 按设置的间隔检查会话；离线时间隔翻倍，恢复后重置。
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut failures = 0u32;
+        let mut last = now();
+        loop {
+            tokio::time::sleep(TICK).await;
+            let settings: KeepAliveSettings =
+                settings::load(&app, SETTINGS_KEY).unwrap_or_default();
+            let elapsed = (now() - last).to_std().unwrap_or_default();
+            if !settings.enabled || elapsed < settings.next_delay(failures) {
+                continue;
+            }
+            last = now();
+            failures = match check(&app, &settings).await {
+                KeepAliveAction::Backoff => failures.saturating_add(1),
+                _ => 0,
+            };
+        }
+    });
+}
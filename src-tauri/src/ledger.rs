@@ -0,0 +1,262 @@
+use crate::rustustc::young::model::CstDateTime;
+use crate::rustustc::young::{SecondClass, Status};
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+// ==================== 学时账本 (HourLedger) ====================
+//
+// 由已参与活动计算各模块（德智体美劳）已获得的学时与毕业要求的差距。
+// 只有 `HourApproved` / `Finished` 的活动计入已获得学时；公示、申请中的计入待定学时。
+
+const UNCATEGORIZED: &str = "未分类";
+
+/// 单个模块的学时要求，按模块名称（`module_dictText`，如「美」）匹配。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModuleRequirement {
+    pub module: String,
+    pub hours: f64,
+}
+
+/// 毕业学时要求。默认值仅作示例，应按所在年级的培养方案修改。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HourRequirements {
+    pub modules: Vec<ModuleRequirement>,
+    /// 总学时要求；为空时取各模块要求之和
+    pub total: Option<f64>,
+}
+
+impl Default for HourRequirements {
+    fn default() -> Self {
+        Self {
+            modules: ["德", "智", "体", "美", "劳"]
+                .iter()
+                .map(|m| ModuleRequirement {
+                    module: m.to_string(),
+                    hours: 2.0,
+                })
+                .collect(),
+            total: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerEntry {
+    pub id: String,
+    pub name: String,
+    pub module: String,
+    pub department: String,
+    pub semester: String,
+    pub hours: f64,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleProgress {
+    pub module: String,
+    pub earned: f64,
+    pub required: f64,
+    pub deficit: f64,
+    pub completed: bool,
+    pub activity_count: usize,
+    /// 公示/申请中、尚未到账的学时
+    pub pending: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupTotal {
+    pub key: String,
+    pub hours: f64,
+    pub activity_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HourSummary {
+    pub total_earned: f64,
+    pub total_required: f64,
+    pub total_deficit: f64,
+    pub total_pending: f64,
+    pub modules: Vec<ModuleProgress>,
+    /// 按学期倒序
+    pub by_semester: Vec<GroupTotal>,
+    /// 按学时降序
+    pub by_department: Vec<GroupTotal>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HourLedger {
+    /// 已获得学时的活动
+    pub entries: Vec<LedgerEntry>,
+    /// 尚在公示或申请中的活动
+    pub pending: Vec<LedgerEntry>,
+}
+
+/// 活动状态是否已发放学时。
+pub fn grants_hours(status: Status) -> bool {
+    matches!(status, Status::HourApproved | Status::Finished)
+}
+
+fn is_pending(status: Status) -> bool {
+    matches!(
+        status,
+        Status::HourPublic | Status::HourAppendPublic | Status::PublicEnded | Status::HourApplying
+    )
+}
+
+/// 学期标签：9 月至次年 1 月为秋季学期，2 至 6 月为春季学期，7、8 月为夏季学期。
+pub fn semester_of(t: &CstDateTime) -> String {
+    let (year, month) = (t.year(), t.month());
+    match month {
+        9..=12 => format!("{}-{} 秋季学期", year, year + 1),
+        1 => format!("{}-{} 秋季学期", year - 1, year),
+        2..=6 => format!("{}-{} 春季学期", year - 1, year),
+        _ => format!("{}-{} 夏季学期", year - 1, year),
+    }
+}
+
+/// 学期标签的先后顺序：学年起始年份，其次秋、春、夏。无法识别的排在最前（倒序时在最后）。
+fn semester_rank(label: &str) -> (i32, u8) {
+    let year = label
+        .split('-')
+        .next()
+        .and_then(|y| y.parse().ok())
+        .unwrap_or(i32::MIN);
+    let term = if label.contains('秋') {
+        0
+    } else if label.contains('春') {
+        1
+    } else {
+        2
+    };
+    (year, term)
+}
+
+fn add_to(groups: &mut Vec<GroupTotal>, key: &str, hours: f64) {
+    match groups.iter_mut().find(|g| g.key == key) {
+        Some(g) => {
+            g.hours += hours;
+            g.activity_count += 1;
+        }
+        None => groups.push(GroupTotal {
+            key: key.to_string(),
+            hours,
+            activity_count: 1,
+        }),
+    }
+}
+
+impl ModuleProgress {
+    fn new(module: &str, required: f64) -> Self {
+        Self {
+            module: module.to_string(),
+            earned: 0.0,
+            required,
+            deficit: 0.0,
+            completed: false,
+            activity_count: 0,
+            pending: 0.0,
+        }
+    }
+}
+
+/// 没有配置要求的模块按要求 0 追加。
+fn progress_of<'a>(modules: &'a mut Vec<ModuleProgress>, module: &str) -> &'a mut ModuleProgress {
+    let i = match modules.iter().position(|m| m.module == module) {
+        Some(i) => i,
+        None => {
+            modules.push(ModuleProgress::new(module, 0.0));
+            modules.len() - 1
+        }
+    };
+    &mut modules[i]
+}
+
+impl LedgerEntry {
+    fn from_activity(sc: &SecondClass) -> Self {
+        Self {
+            id: sc.id.clone(),
+            name: sc.name.clone(),
+            module: sc
+                .module()
+                .map(|m| m.text)
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| UNCATEGORIZED.to_string()),
+            department: sc.department().map(|d| d.name).unwrap_or_default(),
+            semester: sc
+                .hold_time()
+                .map(|t| semester_of(&t.start))
+                .unwrap_or_else(|_| "未知学期".to_string()),
+            hours: sc.hours().unwrap_or(0.0),
+            status: sc.status(),
+        }
+    }
+}
+
+impl HourLedger {
+    pub fn from_participated(activities: &[SecondClass]) -> Self {
+        let mut ledger = Self::default();
+        for sc in activities {
+            let status = sc.status();
+            if grants_hours(status) {
+                ledger.entries.push(LedgerEntry::from_activity(sc));
+            } else if is_pending(status) {
+                ledger.pending.push(LedgerEntry::from_activity(sc));
+            }
+        }
+        ledger
+    }
+
+    pub fn total(&self) -> f64 {
+        self.entries.iter().map(|e| e.hours).sum()
+    }
+
+    pub fn summary(&self, requirements: &HourRequirements) -> HourSummary {
+        // 先列出有要求的模块（保持配置顺序），再追加账本里出现的其他模块
+        let mut modules: Vec<ModuleProgress> = requirements
+            .modules
+            .iter()
+            .map(|r| ModuleProgress::new(&r.module, r.hours))
+            .collect();
+
+        let mut by_semester = Vec::new();
+        let mut by_department = Vec::new();
+        for e in &self.entries {
+            let m = progress_of(&mut modules, &e.module);
+            m.earned += e.hours;
+            m.activity_count += 1;
+            add_to(&mut by_semester, &e.semester, e.hours);
+            if !e.department.is_empty() {
+                add_to(&mut by_department, &e.department, e.hours);
+            }
+        }
+        for e in &self.pending {
+            progress_of(&mut modules, &e.module).pending += e.hours;
+        }
+
+        for m in &mut modules {
+            m.deficit = (m.required - m.earned).max(0.0);
+            m.completed = m.deficit == 0.0;
+        }
+
+        let total_earned = self.total();
+        let total_required = requirements
+            .total
+            .unwrap_or_else(|| requirements.modules.iter().map(|m| m.hours).sum());
+        // 总差距至少是各模块差距之和：其他模块超出的学时不能抵扣
+        let module_deficit: f64 = modules.iter().map(|m| m.deficit).sum();
+        let total_deficit = (total_required - total_earned).max(module_deficit);
+
+        by_semester.sort_by_key(|g| std::cmp::Reverse(semester_rank(&g.key)));
+        by_department.sort_by(|a, b| b.hours.total_cmp(&a.hours));
+
+        HourSummary {
+            total_earned,
+            total_required,
+            total_deficit,
+            total_pending: self.pending.iter().map(|e| e.hours).sum(),
+            modules,
+            by_semester,
+            by_department,
+        }
+    }
+}
@@ -1,16 +1,47 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 pub mod auth;
+pub mod batch;
+pub mod checkin_history;
+pub mod export;
+pub mod favorites;
+pub mod keepalive;
+pub mod ledger;
+pub mod logout;
+pub mod profile;
 pub mod recommend;
+pub mod reminders;
 pub mod rustustc;
+pub mod saved_searches;
 pub mod security;
+pub mod session;
+pub mod settings;
+pub mod sniper;
 pub mod state;
+pub mod waitlist;
+pub mod watcher;
 
+use crate::checkin_history::CheckinRecord;
+use crate::export::ExportFormat;
+use crate::ledger::{HourLedger, HourRequirements};
+use crate::profile::SignProfile;
 use crate::recommend::Recommender;
-use crate::rustustc::young::{SCFilter, SecondClass, YouthService};
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::keepalive::KeepAliveSettings;
+use crate::rustustc::young::model::{CstDateTime, SignInfo, User};
+use crate::rustustc::young::place::{self, CampusSettings};
+use crate::rustustc::young::reminder::ReminderSettings;
+use crate::rustustc::young::saved_search::SavedSearch;
+use crate::rustustc::young::{appeal, conflict, evaluation, work};
+use crate::rustustc::young::{
+    Answer, Appeal, CheckinPayload, ConflictPolicy, EvaluationForm, Query, SCFilter, SecondClass,
+    Series, TagIndex, TagKind, Team, TeamLimits, YouthService,
+};
 use crate::state::AppState;
 use serde_json::json;
+use std::io::Read;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
+use tauri_plugin_fs::{FilePath, FsExt, OpenOptions};
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -33,44 +64,123 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_machine_uid::init())
         .manage(AppState::new())
+        .setup(|app| {
+            watcher::spawn(app.handle().clone());
+            waitlist::spawn(app.handle().clone());
+            reminders::spawn(app.handle().clone());
+            saved_searches::spawn(app.handle().clone());
+            keepalive::spawn(app.handle().clone());
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = sniper::restore(&handle).await;
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             login,
             logout,
             get_login_status,
+            auto_login,
             refresh_session,
             get_unended_activities,
+            search_activities,
+            search_tags,
+            get_tags,
+            get_department_path,
             get_registered_activities,
             get_participated_activities,
+            get_hour_summary,
+            export_participated,
+            export_registered_calendar,
+            get_hour_requirements,
+            set_hour_requirements,
             register_for_activity,
             cancel_activity,
+            list_sign_profiles,
+            save_sign_profile,
+            delete_sign_profile,
+            import_sign_profile,
+            set_activity_remarks,
+            favorite_activity,
+            unfavorite_activity,
+            list_favorites,
+            sync_favorites,
             get_recommended_activities,
             get_activity_children,
+            get_series,
             get_activity_detail,
             get_class_schedule,
-            get_pending_appeals
+            get_pending_appeals,
+            list_appeals,
+            get_appeal_detail,
+            submit_appeal,
+            get_pending_evaluations,
+            get_evaluation_form,
+            submit_evaluation,
+            scan_checkin,
+            get_checkin_history,
+            get_work_submission,
+            submit_work,
+            get_team_info,
+            search_team_members,
+            create_team,
+            join_team,
+            leave_team,
+            disband_team,
+            get_campus_settings,
+            set_campus_settings,
+            get_activity_places,
+            refresh_activity_changes,
+            get_activity_changes,
+            schedule_registration,
+            list_scheduled_registrations,
+            cancel_scheduled_registration,
+            join_waitlist,
+            list_waitlist,
+            leave_waitlist,
+            list_saved_searches,
+            save_search,
+            delete_saved_search,
+            run_saved_search,
+            mark_searches_seen,
+            get_reminder_settings,
+            set_reminder_settings,
+            list_pending_reminders,
+            resolve_conflicts,
+            register_activities,
+            cancel_activities,
+            get_keep_alive_settings,
+            set_keep_alive_settings,
+            check_session
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
-fn map_err<E: ToString>(e: E) -> String {
-    json!({ "code": "INTERNAL_ERROR", "message": e.to_string() }).to_string()
+async fn get_service(state: &State<'_, AppState>) -> Result<Arc<YouthService>, AppError> {
+    state.session.service()
 }
 
-async fn get_service(state: &State<'_, AppState>) -> Result<Arc<YouthService>, String> {
-    let guard = state.youth_service.lock().await;
-    match &*guard {
-        Some(s) => Ok(s.clone()),
-        None => Err(json!({"code": "AUTH_REQUIRED", "message": "Please login first"}).to_string()),
+/// 取缓存的 TagIndex，未加载时从接口拉取一次。
+async fn get_tag_index(
+    state: &State<'_, AppState>,
+    service: &YouthService,
+) -> Result<Arc<TagIndex>, AppError> {
+    let mut guard = state.tag_index.lock().await;
+    if let Some(index) = &*guard {
+        return Ok(index.clone());
     }
+    let index = Arc::new(TagIndex::load(service).await?);
+    *guard = Some(index.clone());
+    Ok(index)
 }
 
 // ==================== 登录相关 (调用 auth 模块) ====================
 
 /// CAS + 二课登录。
 /// - `save=true` 时将账号/密文密码写入插件存储，密钥来源 machine_uid。
-/// - 错误统一包装为 `{code,message}` JSON 字符串，前端需先尝试 JSON.parse。
+/// - 失败时返回 `AppError`，密码错误等为 `CAS_ERROR`。
 #[tauri::command]
 async fn login(
     app: AppHandle,
@@ -78,324 +188,1264 @@ async fn login(
     username: String,
     password: String,
     save: bool,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     // 1. 执行登录
-    let user_info = auth::perform_login(&state, &username, &password)
-        .await
-        .map_err(map_err)?;
+    let user_info = state.session.login(&app, &username, &password).await?;
 
     // 2. 保存加密凭据
     if save {
-        auth::save_credentials(&app, &username, &password).map_err(map_err)?;
+        auth::save_credentials(&app, &username, &password)?;
     } else {
-        auth::clear_credentials(&app).map_err(map_err)?;
+        auth::clear_credentials(&app)?;
     }
 
     Ok(json!(user_info))
 }
 
-/// 查询登录状态：优先复用内存会话，否则尝试读取磁盘密文自动登录。
+/// 登录状态与存储的账号；只读取本地状态，不发起网络请求。
 #[tauri::command]
 async fn get_login_status(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
-    let (logged_in, has_creds, username, user) =
-        auth::try_auto_login(&app, &state).await.map_err(map_err)?;
+) -> Result<serde_json::Value, AppError> {
+    let (stored_username, has_password) = auth::stored_account(&app)?;
+    let session = state.session.session();
+    Ok(json!({
+        "logged_in": session.is_some(),
+        "has_stored_creds": stored_username.is_some() || has_password,
+        "username": session.as_ref().map(|s| s.username.clone()).or(stored_username),
+        "user": session.map(|s| s.user),
+        "session": state.session.state(),
+    }))
+}
 
-    if logged_in {
-        Ok(json!({
-            "logged_in": true,
-            "has_stored_creds": has_creds,
-            "username": username,
-            "user": user
-        }))
-    } else {
-        Ok(json!({
-            "logged_in": false,
-            "has_stored_creds": has_creds,
-            "username": username
-        }))
-    }
+/// 自动登录：内存会话仍有效时直接使用，否则用存储的凭据重新登录。
+/// 与 `login` 串行执行，并发调用不会重复登录。
+#[tauri::command]
+async fn auto_login(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let (logged_in, has_creds, username, user) = auth::try_auto_login(&app).await?;
+    Ok(json!({
+        "logged_in": logged_in,
+        "has_stored_creds": has_creds,
+        "username": username,
+        "user": user,
+        "session": state.session.state(),
+    }))
 }
 
-/// 清空会话并删除存储的密码（用户名保留，便于自动填充）。
+/// 在服务器端注销 CAS 会话，删除存储的密码并清除该账号的本地数据（定时报名、候补、
+/// 提醒、变更记录、收藏缓存）。`forget_account` 为真时还删除用户名、报名信息、
+/// 保存的搜索与签到记录。返回每一步是否成功。
+#[tauri::command(rename_all = "snake_case")]
+async fn logout(
+    app: AppHandle,
+    forget_account: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
+    let report = logout::logout(&app, forget_account.unwrap_or(false)).await;
+    Ok(json!(report))
+}
+
+/// 使用当前 CAS Cookie 刷新 YouthService。若 Cookie 失效返回 `SESSION_EXPIRED`。
 #[tauri::command]
-async fn logout(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
-    *state.cas_client.lock().await = None;
-    *state.youth_service.lock().await = None;
-    auth::clear_credentials(&app).map_err(map_err)?;
-    Ok(())
+async fn refresh_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let user_info = state.session.refresh(&app).await?;
+    Ok(json!({ "success": true, "user": user_info }))
 }
 
-/// 使用当前 CAS Cookie 刷新 YouthService。若 Cookie 失效会返回错误 JSON。
+// ==================== 二课活动相关 (修改筛选逻辑) ====================
+
+/// 获取未结束的活动列表（不展开系列课）。
 #[tauri::command]
-async fn refresh_session(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
-    let cas_guard = state.cas_client.lock().await;
-    let cas_client = match &*cas_guard {
-        Some(c) => c.clone(),
-        None => return Err(map_err("Session expired. Please login again.")),
-    };
-    drop(cas_guard);
+async fn get_unended_activities(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let activities = SecondClass::find(&service, SCFilter::new(), false, false, -1).await?;
+    Ok(favorites::load(&app).annotate_all(&activities))
+}
+
+/// 编译查询语句；用到 `campus:` 时带上用户的校区字典。
+async fn compile_query(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    service: &YouthService,
+    query: &str,
+) -> Result<SCFilter, AppError> {
+    let parsed = Query::parse(query)?;
 
-    if !cas_client.is_login().await {
-        return Err(map_err("CAS Session expired. Please login again."));
+    let index = if parsed.needs_tags() {
+        get_tag_index(state, service).await?
+    } else {
+        Arc::new(TagIndex::default())
+    };
+    let mut filter = parsed.compile(&index)?;
+    if !filter.campuses.is_empty() || !filter.exclude_campuses.is_empty() {
+        let campus: CampusSettings = settings::load(app, CAMPUS_KEY)?;
+        filter = filter.campus_dictionary(campus.dictionary);
     }
+    Ok(filter)
+}
 
-    let new_youth = YouthService::new(&cas_client)
-        .await
-        .map_err(|e| map_err(format!("Failed to refresh: {}", e)))?;
-    let youth_arc = Arc::new(new_youth);
+/// 按查询语句搜索活动，例如 `美 dept:古琴 hours>=2 after:2025-12-01 -label:线上`。
+/// 语法错误或名称无法解析时返回 `QUERY_SYNTAX`，附带字符区间 `span`。
+#[tauri::command]
+async fn search_activities(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    query: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let filter = compile_query(&app, &state, &service, &query).await?;
 
-    *state.youth_service.lock().await = Some(youth_arc.clone());
+    // 状态条件决定查报名中列表、已结束列表或两者
+    let activities = SecondClass::find_by_status(&service, filter).await?;
+    Ok(favorites::load(&app).annotate_all(&activities))
+}
 
-    let user_info = crate::rustustc::young::model::User::get_current(&youth_arc)
-        .await
-        .map_err(map_err)?;
+// ==================== 标签字典 (TagIndex) ====================
 
-    Ok(json!({ "success": true, "user": user_info }))
+/// 模块/部门/标签的模糊搜索（含拼音首字母），供自动补全使用。
+/// `kind` 为 `module` / `department` / `label`，为空时搜索全部。
+#[tauri::command]
+async fn search_tags(
+    state: State<'_, AppState>,
+    query: String,
+    kind: Option<TagKind>,
+    limit: Option<usize>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let index = get_tag_index(&state, &service).await?;
+    Ok(json!(index.search(kind, &query, limit.unwrap_or(10))))
 }
 
-// ==================== 二课活动相关 (修改筛选逻辑) ====================
-
-/// 获取未结束的活动列表（不展开系列课）。
+/// 全部字典条目，供选择器使用；部门带完整路径。
 #[tauri::command]
-async fn get_unended_activities(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+async fn get_tags(state: State<'_, AppState>) -> Result<serde_json::Value, AppError> {
     let service = get_service(&state).await?;
-    let activities = SecondClass::find(&service, SCFilter::new(), false, false, -1)
-        .await
-        .map_err(map_err)?;
-    Ok(json!(activities))
+    let index = get_tag_index(&state, &service).await?;
+    Ok(json!({
+        "modules": index.entries(TagKind::Module),
+        "departments": index.entries(TagKind::Department),
+        "labels": index.entries(TagKind::Label),
+    }))
+}
+
+/// 部门从根节点起的路径；ID 不存在时返回 `NOT_FOUND`。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_department_path(
+    state: State<'_, AppState>,
+    department_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let index = get_tag_index(&state, &service).await?;
+    match index.department_path(&department_id) {
+        Some(path) => Ok(json!(path)),
+        None => Err(AppError::rule(
+            "NOT_FOUND",
+            format!("Department {} not found", department_id),
+        )),
+    }
+}
+
+/// 结束时间+45分钟 > 当前时间（即活动尚未完全结束）视为已报名，否则视为已参与。
+/// 无法解析时间的活动保留在已报名列表。
+fn is_still_registered(sc: &SecondClass, now: CstDateTime) -> bool {
+    match sc.hold_time() {
+        Ok(hold_time) => hold_time.end + chrono::Duration::minutes(45) > now,
+        Err(_) => true,
+    }
 }
 
 /// 获取已报名活动（结束时间+45分钟 > 当前时间）。
 #[tauri::command]
 async fn get_registered_activities(
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     let service = get_service(&state).await?;
 
-    let all_my_activities = SecondClass::get_participated(&service)
-        .await
-        .map_err(map_err)?;
-
-    let now = chrono::Local::now().naive_local();
-    let offset_minutes = chrono::Duration::minutes(45);
+    let all_my_activities = SecondClass::get_participated(&service).await?;
 
+    let now = crate::rustustc::young::model::now();
     let registered_activities: Vec<&SecondClass> = all_my_activities
         .iter()
-        .filter(|sc| {
-            // 结束时间+45分钟 > 当前时间（即活动尚未完全结束）
-            if let Ok(hold_time) = sc.hold_time() {
-                hold_time.end + offset_minutes > now
-            } else {
-                // 无法解析时间的活动，保留在已报名列表
-                true
-            }
-        })
+        .filter(|sc| is_still_registered(sc, now))
         .collect();
 
-    Ok(json!(registered_activities))
+    Ok(favorites::load(&app).annotate_all(registered_activities))
 }
 
 /// 获取已参与活动（结束时间+45分钟 < 当前时间）。
 #[tauri::command]
 async fn get_participated_activities(
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     let service = get_service(&state).await?;
-    let all = SecondClass::get_participated(&service)
-        .await
-        .map_err(map_err)?;
-
-    let now = chrono::Local::now().naive_local();
-    let offset_minutes = chrono::Duration::minutes(45);
+    let all = SecondClass::get_participated(&service).await?;
 
-    // 过滤：结束时间+45分钟 < 当前时间（即活动已完全结束）
+    let now = crate::rustustc::young::model::now();
     let finished: Vec<&SecondClass> = all
         .iter()
-        .filter(|sc| {
-            if let Ok(hold_time) = sc.hold_time() {
-                hold_time.end + offset_minutes < now
-            } else {
-                // 无法解析时间的活动，放入已参与列表
-                false
-            }
-        })
+        .filter(|sc| !is_still_registered(sc, now))
+        .collect();
+
+    Ok(favorites::load(&app).annotate_all(finished))
+}
+
+// ==================== 导出 (export) ====================
+
+/// 导出已参与活动成绩单（`csv` / `json`）。`path` 为空时弹出保存对话框；
+/// 返回写入的路径，用户取消时返回 `null`。
+#[tauri::command]
+async fn export_participated(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    format: ExportFormat,
+    path: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let service = get_service(&state).await?;
+    let all = SecondClass::get_participated(&service).await?;
+    let now = crate::rustustc::young::model::now();
+    let finished: Vec<SecondClass> = all
+        .into_iter()
+        .filter(|sc| !is_still_registered(sc, now))
         .collect();
 
-    Ok(json!(finished))
+    let content = match format {
+        ExportFormat::Csv => export::to_csv(&finished),
+        ExportFormat::Json => export::to_json(&finished)?,
+        ExportFormat::Ics => export::to_ics(&finished, &[]),
+    };
+    export::write_export(&app, &content, format, "第二课堂参与记录", path)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 将已报名活动导出为 `.ics`，`reminders` 为开始前提醒的分钟数（默认 30）。
+#[tauri::command]
+async fn export_registered_calendar(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    reminders: Option<Vec<u32>>,
+    path: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let service = get_service(&state).await?;
+    let all = SecondClass::get_participated(&service).await?;
+    let now = crate::rustustc::young::model::now();
+    let registered: Vec<SecondClass> = all
+        .into_iter()
+        .filter(|sc| is_still_registered(sc, now))
+        .collect();
+
+    let content = export::to_ics(&registered, &reminders.unwrap_or_else(|| vec![30]));
+    export::write_export(&app, &content, ExportFormat::Ics, "第二课堂日程", path)
+        .await
+        .map_err(AppError::from)
+}
+
+// ==================== 学时统计 (HourLedger) ====================
+
+const HOUR_REQUIREMENTS_KEY: &str = "hour_requirements";
+
+/// 各模块已获得学时、要求与差距，另按学期、部门汇总。
+#[tauri::command]
+async fn get_hour_summary(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let participated = SecondClass::get_participated(&service).await?;
+    let requirements: HourRequirements = settings::load(&app, HOUR_REQUIREMENTS_KEY)?;
+    let summary = HourLedger::from_participated(&participated).summary(&requirements);
+    Ok(json!(summary))
+}
+
+#[tauri::command]
+async fn get_hour_requirements(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let requirements: HourRequirements = settings::load(&app, HOUR_REQUIREMENTS_KEY)?;
+    Ok(json!(requirements))
+}
+
+/// 保存学时要求；模块名称需与 `module_dictText` 一致，学时不能为负。
+#[tauri::command]
+async fn set_hour_requirements(
+    app: AppHandle,
+    requirements: HourRequirements,
+) -> Result<(), AppError> {
+    let invalid = requirements
+        .modules
+        .iter()
+        .map(|m| m.hours)
+        .chain(requirements.total)
+        .any(|h| !h.is_finite() || h < 0.0);
+    if invalid {
+        return Err(AppError::rule(
+            "INVALID_ARGUMENT",
+            "Hour requirements must be non-negative",
+        ));
+    }
+    settings::save(&app, HOUR_REQUIREMENTS_KEY, &requirements).map_err(AppError::from)
 }
 
 /// 报名指定活动；会先更新详情再 apply，必要时自动取消冲突活动后重试。
+/// 需要报名信息的活动使用 `profile_id` 指定的（或默认的）报名 profile，`remarks` 覆盖备注；
+/// 没有保存任何 profile 时回退到账号信息。
 #[tauri::command(rename_all = "snake_case")]
 async fn register_for_activity(
+    app: AppHandle,
     state: State<'_, AppState>,
     activity_id: String,
     auto_cancel: bool,
-) -> Result<bool, String> {
-    let service = get_service(&state).await?;
-    // 简单构造 dummy 对象以便调用 update -> apply
-    let mut sc = SecondClass {
-        id: activity_id,
-        name: "".into(),
-        status_code: 0,
-        valid_hour: None,
-        apply_num: None,
-        apply_limit: None,
-        boolean_registration: None,
-        need_sign_info_str: None,
-        conceive: None,
-        base_content: None,
-        item_category: None,
-        create_time_str: None,
-        apply_start: None,
-        apply_end: None,
-        start_time: None,
-        end_time: None,
-        tel: None,
-        raw: serde_json::Value::Null,
+    profile_id: Option<String>,
+    remarks: Option<String>,
+) -> Result<bool, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    let sign_info = if sc.need_sign_info() {
+        profile::resolve_sign_info(
+            &app,
+            &activity_id,
+            profile_id.as_deref(),
+            remarks.as_deref(),
+        )?
+    } else {
+        None
     };
-    sc.update(&service).await.map_err(map_err)?;
-    sc.apply(&service, false, auto_cancel, None).await.map_err(map_err)
+    let applied = sc
+        .apply(&service, false, auto_cancel, sign_info, |id| {
+            profile::resolve_sign_info(&app, id, None, None)
+                .ok()
+                .flatten()
+        })
+        .await?;
+    if applied {
+        reminders::resync_in_background(&app, service);
+    }
+    Ok(applied)
 }
 
 #[tauri::command(rename_all = "snake_case")]
 async fn cancel_activity(
+    app: AppHandle,
     state: State<'_, AppState>,
     activity_id: String,
-) -> Result<bool, String> {
-    let service = get_service(&state).await?;
-    // 简单构造 dummy 对象以便调用 cancel
-    let sc = SecondClass {
-        id: activity_id,
-        name: "".into(),
-        status_code: 0,
-        valid_hour: None,
-        apply_num: None,
-        apply_limit: None,
-        boolean_registration: None,
-        need_sign_info_str: None,
-        conceive: None,
-        base_content: None,
-        item_category: None,
-        create_time_str: None,
-        apply_start: None,
-        apply_end: None,
-        start_time: None,
-        end_time: None,
-        tel: None,
-        raw: serde_json::Value::Null,
-    };
+) -> Result<bool, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::from_id(&activity_id);
+    let cancelled = sc.cancel_apply(&service).await?;
+    if cancelled {
+        reminders::resync_in_background(&app, service);
+    }
+    Ok(cancelled)
+}
+
+// ==================== 报名信息 (SignProfile) ====================
 
-    sc.cancel_apply(&service).await.map_err(map_err)
+/// 本地保存的报名信息列表（解密后返回）。
+#[tauri::command]
+async fn list_sign_profiles(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let profiles = profile::list_profiles(&app)?;
+    Ok(json!(profiles))
 }
 
+/// 新建（`id` 为空）或更新报名信息；校验失败返回 `INVALID_SIGN_INFO`，`fields` 为逐字段错误。
+#[tauri::command]
+async fn save_sign_profile(
+    app: AppHandle,
+    profile: SignProfile,
+) -> Result<serde_json::Value, AppError> {
+    let saved = profile::save_profile(&app, profile)?;
+    Ok(json!(saved))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn delete_sign_profile(app: AppHandle, profile_id: String) -> Result<(), AppError> {
+    profile::delete_profile(&app, &profile_id).map_err(AppError::from)
+}
+
+/// 用账号信息（学院、班级、手机号）预填一份报名信息，不保存。
+#[tauri::command]
+async fn import_sign_profile(state: State<'_, AppState>) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let info = SignInfo::get_self(&service).await?;
+    Ok(json!({ "id": "", "name": "默认", "info": info, "is_default": false }))
+}
+
+/// 保存某活动的报名备注，报名时自动带上；空字符串表示删除。
+#[tauri::command(rename_all = "snake_case")]
+async fn set_activity_remarks(
+    app: AppHandle,
+    activity_id: String,
+    remarks: String,
+) -> Result<(), AppError> {
+    profile::set_activity_remarks(&app, &activity_id, &remarks).map_err(AppError::from)
+}
+
+// ==================== 收藏 (Favorite) ====================
+
+/// 收藏活动；网络问题、会话失效或收藏接口不可用时先保存在本地，返回 `{synced}`；规则类错误按原错误码返回。
+#[tauri::command(rename_all = "snake_case")]
+async fn favorite_activity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let synced = favorites::set_favorite(&app, &service, &activity_id, true).await?;
+    Ok(json!({ "synced": synced }))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn unfavorite_activity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let synced = favorites::set_favorite(&app, &service, &activity_id, false).await?;
+    Ok(json!({ "synced": synced }))
+}
+
+/// 收藏列表：先补发本地未同步的操作，再合并服务器列表；离线时返回本地快照。
+#[tauri::command]
+async fn list_favorites(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let cache = favorites::sync(&app, &service).await?;
+    Ok(cache.annotate_all(&cache.items))
+}
 
-/// 基于历史参与记录的简单推荐（TF/部门/模块加权）。
+/// 手动同步收藏，返回剩余未同步的操作数。
+#[tauri::command]
+async fn sync_favorites(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let cache = favorites::sync(&app, &service).await?;
+    Ok(json!({ "pending": cache.pending.len() }))
+}
+
+/// 基于历史参与记录的简单推荐（TF/部门/模块加权，跨校区降权）。
 #[tauri::command]
 async fn get_recommended_activities(
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     let service = get_service(&state).await?;
-    let rec_list = Recommender::recommend(&service, 10)
-        .await
-        .map_err(map_err)?;
-    Ok(json!(rec_list))
+    let campus: CampusSettings = settings::load(&app, CAMPUS_KEY)?;
+    let rec_list =
+        Recommender::recommend_near(&service, 10, campus.home_campus, &campus.dictionary).await?;
+    Ok(favorites::load(&app).annotate_all(&rec_list))
 }
 
 /// 获取系列课的子项目；非系列课返回 `NOT_A_SERIES` 错误。
 #[tauri::command(rename_all = "snake_case")]
 async fn get_activity_children(
+    app: AppHandle,
     state: State<'_, AppState>,
     activity_id: String,
-) -> Result<serde_json::Value, String> {
-    let service = get_service(&state).await?;
-
-    // 1. 构造一个只有 ID 的对象
-    let mut sc = SecondClass {
-        id: activity_id,
-        name: "".into(),
-        status_code: 0,
-        valid_hour: None,
-        apply_num: None,
-        apply_limit: None,
-        boolean_registration: None,
-        need_sign_info_str: None,
-        conceive: None,
-        base_content: None,
-        item_category: None,
-        create_time_str: None,
-        apply_start: None,
-        apply_end: None,
-        start_time: None,
-        end_time: None,
-        tel: None,
-        raw: serde_json::Value::Null,
-    };
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
 
-    // 2. 更新详情 (这一步是为了获取 is_series 标志，以及确保 ID 有效)
-    sc.update(&service).await.map_err(map_err)?;
+    // 详情中带有 is_series 标志，同时确认 ID 有效
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
 
     if !sc.is_series() {
-        return Err(json!({
-            "code": "NOT_A_SERIES",
-            "message": "The specified activity is not a series activity."
-        })
-        .to_string());
+        return Err(AppError::rule(
+            "NOT_A_SERIES",
+            "The specified activity is not a series activity.",
+        ));
     }
 
     // 3. 获取子项目
     // 注意：get_children 内部会检查 is_series()，如果不是系列课会返回空列表
-    let children = sc.get_children(&service).await.map_err(map_err)?;
+    let children = sc.get_children(&service).await?;
+
+    Ok(favorites::load(&app).annotate_all(&children))
+}
+
+/// 系列课汇总：各状态数量、已报名/已参加场次、学时合计与下一场；子项目详情并发展开。
+/// 非系列课返回 `NOT_A_SERIES` 错误。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_series(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let parent = SecondClass::get_by_id(&service, &activity_id).await?;
+
+    if !parent.is_series() {
+        return Err(AppError::rule(
+            "NOT_A_SERIES",
+            "The specified activity is not a series activity.",
+        ));
+    }
 
-    Ok(json!(children))
+    let series = Series::load(&service, parent).await?;
+    let cache = favorites::load(&app);
+    let mut value = json!(series);
+    value["parent"] = cache.annotate(&series.parent);
+    for (i, session) in series.sessions.iter().enumerate() {
+        value["sessions"][i]["activity"] = cache.annotate(&session.activity);
+    }
+    Ok(value)
 }
 
 #[tauri::command(rename_all = "snake_case")]
 async fn get_activity_detail(
+    app: AppHandle,
     state: State<'_, AppState>,
     activity_id: String,
-) -> Result<serde_json::Value, String> {
-    let service = get_service(&state).await?;
-
-    // 1. 构造 dummy 对象
-    let mut sc = SecondClass {
-        id: activity_id,
-        name: "".into(),
-        status_code: 0,
-        valid_hour: None,
-        apply_num: None,
-        apply_limit: None,
-        boolean_registration: None,
-        need_sign_info_str: None,
-        conceive: None,
-        base_content: None,
-        item_category: None,
-        create_time_str: None,
-        apply_start: None,
-        apply_end: None,
-        start_time: None,
-        end_time: None,
-        tel: None,
-        raw: serde_json::Value::Null,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    Ok(favorites::load(&app).annotate(&sc))
+}
+
+/// 读取前端选择的本地文件（路径或移动端 content URI），返回 (文件名, 内容)。
+/// 先检查文件数，再按元数据检查每个文件的大小，都通过后才读取内容。
+fn read_local_files(
+    app: &AppHandle,
+    paths: Vec<String>,
+    max_files: usize,
+    max_size: usize,
+) -> Result<Vec<(String, Vec<u8>)>, AppError> {
+    let bad_request =
+        |path: &str, e: std::io::Error| AppError::rule("BAD_REQUEST", format!("{}: {}", path, e));
+    if paths.len() > max_files {
+        return Err(AppError::rule(
+            "BAD_REQUEST",
+            format!("At most {} files are allowed", max_files),
+        ));
+    }
+
+    let mut opened = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let name = path
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or("attachment")
+            .to_string();
+        let file_path = path.parse::<FilePath>().unwrap_or_else(|e| match e {});
+        let mut options = OpenOptions::new();
+        options.read(true);
+        let file = app
+            .fs()
+            .open(file_path, options)
+            .map_err(|e| bad_request(path, e))?;
+        let size = file.metadata().map_err(|e| bad_request(path, e))?.len();
+        if size > max_size as u64 {
+            return Err(AppError::rule(
+                "BAD_REQUEST",
+                format!("File {} exceeds {} MB", name, max_size / (1024 * 1024)),
+            ));
+        }
+        opened.push((path, name, file, size));
+    }
+
+    let mut files = Vec::with_capacity(opened.len());
+    for (path, name, file, size) in opened {
+        // 元数据大小不准时（如部分 content URI）最多多读 1 字节，交给后续校验拒绝
+        let mut bytes = Vec::with_capacity(size as usize);
+        file.take(max_size as u64 + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| bad_request(path, e))?;
+        files.push((name, bytes));
+    }
+    Ok(files)
+}
+
+// ==================== 学时申诉 (Appeal) ====================
+
+fn appeal_json(appeal: &Appeal) -> serde_json::Value {
+    json!({
+        "appeal": appeal,
+        "status": appeal.status(),
+        "opinion": appeal.opinion(),
+        "attachments": appeal.attachments(),
+    })
+}
+
+/// 审核中的申诉记录，格式同 `list_appeals`。
+#[tauri::command]
+async fn get_pending_appeals(state: State<'_, AppState>) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let appeals = Appeal::list(&service).await?;
+    Ok(json!(Appeal::pending(&appeals)
+        .iter()
+        .map(appeal_json)
+        .collect::<Vec<_>>()))
+}
+
+/// 已提交的申诉记录。
+#[tauri::command]
+async fn list_appeals(state: State<'_, AppState>) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let appeals = Appeal::list(&service).await?;
+    Ok(json!(appeals.iter().map(appeal_json).collect::<Vec<_>>()))
+}
+
+/// 申诉详情，附带对应活动与其学时驳回原因。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_appeal_detail(
+    state: State<'_, AppState>,
+    appeal_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let appeal = Appeal::get_by_id(&service, &appeal_id).await?;
+    let activity = match appeal.item_id.as_deref() {
+        Some(id) => SecondClass::get_by_id(&service, id).await.ok(),
+        None => None,
     };
 
-    // 2. 调用 update 从服务器获取最新详情
-    sc.update(&service).await.map_err(map_err)?;
+    let mut detail = appeal_json(&appeal);
+    detail["reject_reason"] = json!(activity.as_ref().and_then(|sc| sc.hour_reject_reason()));
+    detail["activity"] = json!(activity);
+    Ok(detail)
+}
+
+/// 提交申诉。`attachments` 为本地文件路径（或移动端 content URI），最多 3 个、每个不超过 10 MB。
+#[tauri::command(rename_all = "snake_case")]
+async fn submit_appeal(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+    reason: String,
+    attachments: Option<Vec<String>>,
+) -> Result<(), AppError> {
+    let service = get_service(&state).await?;
+
+    let files = read_local_files(
+        &app,
+        attachments.unwrap_or_default(),
+        appeal::MAX_ATTACHMENTS,
+        appeal::MAX_ATTACHMENT_SIZE,
+    )?;
+    if let Err(e) = Appeal::validate(&reason, &files) {
+        return Err(AppError::rule("BAD_REQUEST", e));
+    }
 
-    // 3. 返回完整的对象
-    Ok(json!(sc))
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    if !appeal::appealable(sc.status()) {
+        return Err(AppError::rule(
+            "APPEAL_UNAVAILABLE",
+            format!(
+                "Activity status {} does not allow appeals",
+                sc.status().text()
+            ),
+        ));
+    }
+    Appeal::submit(&service, &sc, &reason, files)
+        .await
+        .map_err(AppError::from)
 }
 
-//TODO
+// ==================== 活动评价 (Evaluation) ====================
 
+/// 已结束、开启评价且尚未评价的已参与活动。
 #[tauri::command]
-async fn get_class_schedule() -> Result<serde_json::Value, String> {
-    Ok(json!([]))
+async fn get_pending_evaluations(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let participated = SecondClass::get_participated(&service).await?;
+    let pending = evaluation::pending(&participated, crate::rustustc::young::model::now());
+    Ok(favorites::load(&app).annotate_all(&pending))
 }
 
+/// 评价表：题目列表，`kind.type` 为 `rating` / `single` / `multiple` / `text`。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_evaluation_form(
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let form = EvaluationForm::fetch(&service, &activity_id).await?;
+    Ok(json!(form))
+}
+
+/// 提交评价；校验失败返回 `INVALID_ANSWERS`，`fields` 为逐题错误。
+#[tauri::command(rename_all = "snake_case")]
+async fn submit_evaluation(
+    state: State<'_, AppState>,
+    activity_id: String,
+    answers: Vec<Answer>,
+) -> Result<(), AppError> {
+    let service = get_service(&state).await?;
+    let form = EvaluationForm::fetch(&service, &activity_id).await?;
+    let errors = form.validate(&answers);
+    if !errors.is_empty() {
+        return Err(
+            AppError::rule("INVALID_ANSWERS", "Evaluation answers validation failed")
+                .with_detail(json!({ "fields": errors })),
+        );
+    }
+    form.submit(&service, &answers)
+        .await
+        .map_err(AppError::from)
+}
+
+// ==================== 扫码签到 (Checkin) ====================
+
+/// 扫码签到 / 签退。`payload` 为二维码解码后的字符串；`activity_id` 为用户所选活动，
+/// 为空时取二维码中的活动。校验失败返回 `CHECKIN_INVALID`（`reason` 区分原因），
+/// 校验失败与提交结果都会写入本地记录。
+#[tauri::command(rename_all = "snake_case")]
+async fn scan_checkin(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: String,
+    activity_id: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let invalid = |e: &dyn std::fmt::Display, detail: serde_json::Value| {
+        AppError::rule("CHECKIN_INVALID", e).with_detail(detail)
+    };
+
+    let service = get_service(&state).await?;
+    let parsed = CheckinPayload::parse(&payload).map_err(|e| invalid(&e, json!(e)))?;
+    let target = activity_id.unwrap_or_else(|| parsed.item_id.clone());
+    let sc = SecondClass::get_by_id(&service, &target).await?;
+
+    let now = crate::rustustc::young::model::now();
+    let result = match parsed.validate(&sc, now) {
+        Ok(()) => parsed.submit(&service).await.map_err(AppError::from),
+        Err(e) => Err(invalid(&e, json!(e))),
+    };
+
+    let entry = CheckinRecord {
+        activity_id: sc.id.clone(),
+        activity_name: sc.name.clone(),
+        kind: parsed.kind,
+        time: now,
+        success: result.is_ok(),
+        message: result.as_ref().err().map(|e| e.message().to_string()),
+    };
+    checkin_history::record(&app, entry.clone())?;
+    result.map(|_| json!(entry))
+}
+
+/// 本地签到 / 签退记录，最近的在前。
+#[tauri::command]
+async fn get_checkin_history(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let records = checkin_history::list(&app)?;
+    Ok(json!(records))
+}
+
+// ==================== 作品提交 (Work) ====================
+
+/// 作品提交状态：`window`（是否可提交、截止时间、原因）、审核状态与已提交文件。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_work_submission(
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    let submission =
+        work::WorkSubmission::load(&service, &sc, crate::rustustc::young::model::now()).await?;
+    Ok(json!(submission))
+}
+
+/// 上传并提交作品，返回服务器存储路径。截止时间按 `attaEndTime` 在本地检查，
+/// 不可提交时返回 `SUBMISSION_CLOSED`，文件不合规返回 `BAD_REQUEST`。
+#[tauri::command(rename_all = "snake_case")]
+async fn submit_work(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+    files: Vec<String>,
+    remark: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    let now = crate::rustustc::young::model::now();
+
+    let window = work::window(&sc, now);
+    if !window.open {
+        return Err(
+            AppError::rule("SUBMISSION_CLOSED", window.reason.unwrap_or_default())
+                .with_detail(json!({ "deadline": window.deadline })),
+        );
+    }
+    let files = read_local_files(&app, files, work::MAX_WORK_FILES, work::MAX_WORK_FILE_SIZE)?;
+    if let Err(e) = work::validate_files(&files) {
+        return Err(AppError::rule("BAD_REQUEST", e));
+    }
+
+    let paths = work::WorkSubmission::submit(&service, &sc, files, remark.as_deref(), now).await?;
+    Ok(json!(paths))
+}
+
+// ==================== 组队报名 (Team) ====================
+
+/// 取活动详情与组队限制；非组队活动返回 `NOT_A_TEAM_ACTIVITY`。
+async fn get_team_activity(
+    service: &YouthService,
+    activity_id: &str,
+) -> Result<(SecondClass, TeamLimits), AppError> {
+    let sc = SecondClass::get_by_id(service, activity_id).await?;
+    let limits = TeamLimits::require(&sc)?;
+    Ok((sc, limits))
+}
+
+async fn get_my_team(service: &YouthService, activity_id: &str) -> Result<Team, AppError> {
+    Team::mine(service, activity_id)
+        .await?
+        .ok_or_else(|| AppError::rule("TEAM_INVALID", "尚未加入队伍"))
+}
+
+/// 组队限制与当前用户的队伍：`{limits, team, is_leader}`。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_team_info(
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let (_, limits) = get_team_activity(&service, &activity_id).await?;
+    let (team, user) = tokio::try_join!(
+        Team::mine(&service, &activity_id),
+        User::get_current(&service)
+    )?;
+    let is_leader = team.as_ref().is_some_and(|t| t.is_leader(&user.id));
+    Ok(json!({ "limits": limits, "team": team, "is_leader": is_leader }))
+}
+
+/// 按姓名或学号查找队员。
+#[tauri::command]
+async fn search_team_members(
+    state: State<'_, AppState>,
+    query: String,
+    limit: Option<i32>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let users = User::find(&service, query.trim(), limit.unwrap_or(10), 10).await?;
+    Ok(json!(users))
+}
+
+/// 以当前用户为队长建队并报名；人数或队伍数不符合时返回 `TEAM_INVALID`。
+/// `member_ids` 为 `search_team_members` 结果中的 `id`（用户 ID），不是学号。
+#[tauri::command(rename_all = "snake_case")]
+async fn create_team(
+    state: State<'_, AppState>,
+    activity_id: String,
+    name: String,
+    member_ids: Vec<String>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let (sc, user) = tokio::try_join!(
+        SecondClass::get_by_id(&service, &activity_id),
+        User::get_current(&service)
+    )?;
+    let team = Team::create(&service, &sc, &user.id, &name, &member_ids).await?;
+    Ok(json!(team))
+}
+
+/// 凭邀请码加入队伍，返回加入后的队伍；队伍已满或邀请码不属于该活动时返回 `TEAM_INVALID`。
+#[tauri::command(rename_all = "snake_case")]
+async fn join_team(
+    state: State<'_, AppState>,
+    activity_id: String,
+    invite_code: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let (sc, user) = tokio::try_join!(
+        SecondClass::get_by_id(&service, &activity_id),
+        User::get_current(&service)
+    )?;
+    let team = Team::join(&service, &sc, &user.id, &invite_code).await?;
+    Ok(json!(team))
+}
+
+/// 退出当前队伍（队长需解散）。
+#[tauri::command(rename_all = "snake_case")]
+async fn leave_team(state: State<'_, AppState>, activity_id: String) -> Result<(), AppError> {
+    let service = get_service(&state).await?;
+    let team = get_my_team(&service, &activity_id).await?;
+    let user = User::get_current(&service).await?;
+    team.leave(&service, &user.id).await.map_err(AppError::from)
+}
+
+/// 解散当前队伍，仅队长可操作。
+#[tauri::command(rename_all = "snake_case")]
+async fn disband_team(state: State<'_, AppState>, activity_id: String) -> Result<(), AppError> {
+    let service = get_service(&state).await?;
+    let team = get_my_team(&service, &activity_id).await?;
+    let user = User::get_current(&service).await?;
+    team.disband(&service, &user.id)
+        .await
+        .map_err(AppError::from)
+}
+
+// ==================== 校区 (Campus) ====================
+
+const CAMPUS_KEY: &str = "campus";
+
+/// 常驻校区与校区识别字典。
+#[tauri::command]
+async fn get_campus_settings(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let campus: CampusSettings = settings::load(&app, CAMPUS_KEY)?;
+    Ok(json!(campus))
+}
+
+/// 保存校区设置；字典规则的关键词不能全为空。
+#[tauri::command]
+async fn set_campus_settings(app: AppHandle, campus: CampusSettings) -> Result<(), AppError> {
+    let empty_rule = campus
+        .dictionary
+        .rules
+        .iter()
+        .any(|r| r.keywords.iter().all(|k| k.trim().is_empty()));
+    if empty_rule {
+        return Err(AppError::rule(
+            "INVALID_ARGUMENT",
+            "Campus rules must have at least one keyword",
+        ));
+    }
+    settings::save(&app, CAMPUS_KEY, &campus).map_err(AppError::from)
+}
+
+/// 活动的全部地点、场次时间与识别出的校区。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_activity_places(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    let campus: CampusSettings = settings::load(&app, CAMPUS_KEY)?;
+    Ok(json!(place::places(&sc, &campus.dictionary)))
+}
+
+// ==================== 变更检测 (Watcher) ====================
+
+/// 立即刷新已报名活动的快照，返回新检测到的变更（同时发送通知与 `activity://changed` 事件）。
+#[tauri::command]
+async fn refresh_activity_changes(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let events = watcher::refresh(&app, &service).await?;
+    Ok(json!(events))
+}
+
+/// 变更记录（最近的在前）；不指定 `activity_id` 时返回全部。
+#[tauri::command(rename_all = "snake_case")]
+async fn get_activity_changes(
+    app: AppHandle,
+    activity_id: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let events = watcher::changes(&app, activity_id.as_deref())?;
+    Ok(json!(events))
+}
+
+// ==================== 定时报名 (Sniper) ====================
+
+/// 在报名开放瞬间自动报名；活动已报名、报名已开放或已有任务时返回 `SCHEDULE_INVALID`。
+#[tauri::command(rename_all = "snake_case")]
+async fn schedule_registration(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+    profile_id: Option<String>,
+    auto_cancel: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let job = sniper::schedule(
+        &app,
+        &service,
+        &activity_id,
+        profile_id,
+        auto_cancel.unwrap_or(false),
+    )
+    .await?;
+    Ok(json!(job))
+}
+
+/// 全部定时任务与当前估计的服务器时钟偏差（毫秒，服务器 − 本地）。
+#[tauri::command]
+async fn list_scheduled_registrations(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let jobs = sniper::list(&app)?;
+    let skew = state.session.current_service().and_then(|s| s.clock_skew());
+    Ok(json!({ "jobs": jobs, "clock_skew_ms": skew }))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn cancel_scheduled_registration(
+    app: AppHandle,
+    activity_id: String,
+) -> Result<bool, AppError> {
+    sniper::cancel(&app, &activity_id)
+        .await
+        .map_err(AppError::from)
+}
+
+// ==================== 候补 (Waitlist) ====================
+
+/// 加入候补：有人取消报名时自动报名，报名截止后停止。已报名、已截止或已在候补中时返回 `WAITLIST_INVALID`。
+#[tauri::command(rename_all = "snake_case")]
+async fn join_waitlist(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+    profile_id: Option<String>,
+    auto_cancel: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let entry = waitlist::join(
+        &app,
+        &service,
+        &activity_id,
+        profile_id,
+        auto_cancel.unwrap_or(false),
+    )
+    .await?;
+    Ok(json!(entry))
+}
+
+#[tauri::command]
+async fn list_waitlist(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let entries = waitlist::list(&app)?;
+    Ok(json!(entries))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn leave_waitlist(app: AppHandle, activity_id: String) -> Result<bool, AppError> {
+    waitlist::leave(&app, &activity_id).map_err(AppError::from)
+}
+
+// ==================== 保存的搜索 (SavedSearch) ====================
+
+#[tauri::command]
+async fn list_saved_searches(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let searches = saved_searches::list(&app)?;
+    Ok(json!(searches))
+}
+
+/// 新建（`id` 为空）或更新保存的搜索；查询语句错误返回 `QUERY_SYNTAX`。
+/// 修改查询语句会清空已读记录，下次执行重新建立基线。
+#[tauri::command]
+async fn save_search(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: Option<String>,
+    name: String,
+    query: String,
+    notify: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let filter = compile_query(&app, &state, &service, &query).await?;
+    let search = SavedSearch {
+        id: id.unwrap_or_default(),
+        name: if name.trim().is_empty() {
+            query.trim().to_string()
+        } else {
+            name.trim().to_string()
+        },
+        query,
+        filter,
+        notify: notify.unwrap_or(true),
+        seen: Default::default(),
+        unseen: Vec::new(),
+        last_run_at: None,
+    };
+    let saved = saved_searches::save(&app, search)?;
+    Ok(json!(saved))
+}
+
+#[tauri::command]
+async fn delete_saved_search(app: AppHandle, id: String) -> Result<(), AppError> {
+    saved_searches::delete(&app, &id).map_err(AppError::from)
+}
+
+/// 立即执行一个保存的搜索：`{activities, new_ids}`。
+#[tauri::command]
+async fn run_saved_search(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let (activities, new_ids) = saved_searches::run(&app, &service, &id).await?;
+    Ok(json!({
+        "activities": favorites::load(&app).annotate_all(&activities),
+        "new_ids": new_ids
+    }))
+}
+
+/// 将新匹配全部标为已读；不指定 `id` 时处理全部搜索。
+#[tauri::command]
+async fn mark_searches_seen(app: AppHandle, id: Option<String>) -> Result<(), AppError> {
+    saved_searches::mark_seen(&app, id.as_deref()).map_err(AppError::from)
+}
+
+// ==================== 活动提醒 (Reminder) ====================
+
+/// 提醒设置：是否启用与提前的分钟数（默认 1 天与 1 小时）。
+#[tauri::command]
+async fn get_reminder_settings(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let reminder: ReminderSettings = settings::load(&app, reminders::SETTINGS_KEY)?;
+    Ok(json!(reminder))
+}
+
+/// 保存提醒设置；已登录时立即按新设置重排提醒。
+#[tauri::command]
+async fn set_reminder_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    reminder: ReminderSettings,
+) -> Result<(), AppError> {
+    reminder
+        .validate()
+        .map_err(|e| AppError::rule("INVALID_ARGUMENT", e))?;
+    settings::save(&app, reminders::SETTINGS_KEY, &reminder)?;
+    if let Some(service) = state.session.current_service() {
+        reminders::resync_in_background(&app, service);
+    }
+    Ok(())
+}
+
+/// 尚未发送的提醒，按提醒时间排序。
+#[tauri::command]
+async fn list_pending_reminders(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let pending = reminders::pending(&app)?;
+    Ok(json!(pending))
+}
+
+// ==================== 冲突处理 (Conflict) ====================
+
+/// 按策略处理时间冲突后报名：列出重叠的已报名活动并决定取消哪些。
+/// `dry_run` 或计划不可行时只返回计划（`outcome` 为 `null`），不做任何修改；
+/// 执行时报名失败会恢复已取消的报名，`outcome` 如实记录每一步。
+#[tauri::command(rename_all = "snake_case")]
+async fn resolve_conflicts(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_id: String,
+    policy: Option<ConflictPolicy>,
+    dry_run: bool,
+    profile_id: Option<String>,
+    remarks: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let sc = SecondClass::get_by_id(&service, &activity_id).await?;
+    let sign_info = if sc.need_sign_info() && !dry_run {
+        profile::resolve_sign_info(
+            &app,
+            &activity_id,
+            profile_id.as_deref(),
+            remarks.as_deref(),
+        )?
+    } else {
+        None
+    };
+    // 取消前按各活动保存的备注与默认 profile 记录报名信息，回滚时用它重新报名
+    let (plan, outcome) = conflict::plan_and_resolve(
+        &service,
+        &sc,
+        &policy.unwrap_or_default(),
+        false,
+        sign_info,
+        dry_run,
+        |id| {
+            profile::resolve_sign_info(&app, id, None, None)
+                .ok()
+                .flatten()
+        },
+    )
+    .await?;
+    if outcome
+        .as_ref()
+        .is_some_and(|o| o.applied || !o.cancelled.is_empty())
+    {
+        reminders::resync_in_background(&app, service);
+    }
+    Ok(json!({ "plan": plan, "outcome": outcome }))
+}
+
+// ==================== 批量操作 (Batch) ====================
+
+/// 按顺序批量报名；批次内时间重叠的活动只报名靠前的一个。
+/// 每处理完一个活动发送 `batch://progress` 事件，最终返回逐个活动的结果。
+#[tauri::command(rename_all = "snake_case")]
+async fn register_activities(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_ids: Vec<String>,
+    profile_id: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let items = batch::register(&app, &service, &activity_ids, profile_id.as_deref()).await?;
+    reminders::resync_in_background(&app, service);
+    Ok(json!(items))
+}
+
+/// 批量取消报名；已开始的活动不取消（`closed`）。
+#[tauri::command(rename_all = "snake_case")]
+async fn cancel_activities(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    activity_ids: Vec<String>,
+) -> Result<serde_json::Value, AppError> {
+    let service = get_service(&state).await?;
+    let items = batch::cancel(&app, &service, &activity_ids).await?;
+    reminders::resync_in_background(&app, service);
+    Ok(json!(items))
+}
+
+// ==================== 会话保活 (KeepAlive) ====================
+
+/// 保活设置：检查间隔、主动刷新时间与离线时的最长间隔。
+#[tauri::command]
+async fn get_keep_alive_settings(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    let keep_alive: KeepAliveSettings = settings::load(&app, keepalive::SETTINGS_KEY)?;
+    Ok(json!(keep_alive))
+}
+
+/// 保存保活设置，下一次检查起生效。
+#[tauri::command(rename_all = "snake_case")]
+async fn set_keep_alive_settings(
+    app: AppHandle,
+    keep_alive: KeepAliveSettings,
+) -> Result<(), AppError> {
+    keep_alive
+        .validate()
+        .map_err(|e| AppError::rule("INVALID_ARGUMENT", e))?;
+    settings::save(&app, keepalive::SETTINGS_KEY, &keep_alive)?;
+    Ok(())
+}
+
+/// 立即检查会话（如应用回到前台时），按需刷新或重新登录。
+#[tauri::command]
+async fn check_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let keep_alive: KeepAliveSettings = settings::load(&app, keepalive::SETTINGS_KEY)?;
+    let action = keepalive::check(&app, &keep_alive).await;
+    Ok(json!({ "action": action, "session": state.session.state() }))
+}
+
+//TODO
+
 #[tauri::command]
-async fn get_pending_appeals() -> Result<serde_json::Value, String> {
+async fn get_class_schedule() -> Result<serde_json::Value, AppError> {
     Ok(json!([]))
 }
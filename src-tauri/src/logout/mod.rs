@@ -0,0 +1,80 @@
+pub mod report;
+
+pub use report::{account_changed, LogoutReport, LogoutStep};
+
+use anyhow::Result;
+use tauri::{AppHandle, Manager};
+
+use crate::state::AppState;
+use crate::{
+    auth, checkin_history, favorites, profile, reminders, saved_searches, sniper, waitlist, watcher,
+};
+
+// ==================== 登出 (Logout) ====================
+//
+// 在服务器端注销 CAS 会话，并清除本地与该账号相关的数据。各步骤独立执行，
+// 某一步失败不影响其余步骤，结果逐项返回。设备设置（`settings.json`）保留。
+// 报名信息、保存的搜索与签到记录在普通登出时保留，但只属于记录的账号（`auth::data_owner`），
+// 换另一个账号登录时先由 `switch_account` 清除。
+
+/// 清除后台任务与缓存；`all` 为真时还清除报名信息、保存的搜索与签到记录。
+async fn clear_account_data(app: &AppHandle, report: &mut LogoutReport, all: bool) {
+    *app.state::<AppState>().tag_index.lock().await = None;
+    report.record("sniper_jobs", sniper::clear(app).await);
+    report.record("waitlist", waitlist::clear(app));
+    report.record("reminders", reminders::clear(app));
+    report.record("watcher", watcher::clear(app));
+    report.record("favorites", favorites::clear(app));
+    #[cfg(mobile)]
+    {
+        use tauri_plugin_notification::NotificationExt;
+        report.record(
+            "notifications",
+            app.notification()
+                .remove_all_active()
+                .map_err(anyhow::Error::from),
+        );
+    }
+
+    if all {
+        report.record("sign_profiles", profile::clear(app));
+        report.record("saved_searches", saved_searches::clear(app));
+        report.record("checkin_history", checkin_history::clear(app));
+    }
+}
+
+/// 登出。`forget_account` 为真时还删除用户名、报名信息、保存的搜索与签到记录。
+/// 未登录时跳过服务器端注销，只清除本地数据。
+pub async fn logout(app: &AppHandle, forget_account: bool) -> LogoutReport {
+    let state = app.state::<AppState>();
+    let mut report = LogoutReport::default();
+
+    // 先结束会话，后台任务随即不再使用它
+    if let Some(session) = state.session.logout(app).await {
+        report.record("cas_logout", session.cas.logout().await);
+        report.record("cookies", session.cas.clear_cookies());
+    }
+
+    if forget_account {
+        report.record("credentials", auth::forget_account(app));
+    } else {
+        report.record("credentials", auth::clear_credentials(app));
+    }
+    clear_account_data(app, &mut report, forget_account).await;
+    report
+}
+
+/// 登录成功后调用：本地数据属于另一个账号时全部清除，再把数据记到 `username` 名下。
+/// 返回执行的清除步骤；账号未变时为 `None`。
+pub async fn switch_account(app: &AppHandle, username: &str) -> Result<Option<LogoutReport>> {
+    let owner = auth::data_owner(app)?;
+    let report = if account_changed(owner.as_deref(), username) {
+        let mut report = LogoutReport::default();
+        clear_account_data(app, &mut report, true).await;
+        Some(report)
+    } else {
+        None
+    };
+    auth::set_data_owner(app, username)?;
+    Ok(report)
+}
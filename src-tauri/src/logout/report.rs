@@ -0,0 +1,50 @@
+use anyhow::Result;
+use serde::Serialize;
+
+// ==================== 登出结果 (LogoutReport) ====================
+//
+// 登出与切换账号时逐项记录清除步骤的结果，不涉及存储读写。
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogoutStep {
+    pub step: &'static str,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogoutReport {
+    /// 所有步骤都成功
+    pub ok: bool,
+    pub steps: Vec<LogoutStep>,
+}
+
+impl Default for LogoutReport {
+    fn default() -> Self {
+        Self {
+            ok: true,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl LogoutReport {
+    pub fn record(&mut self, step: &'static str, result: Result<()>) {
+        self.steps.push(LogoutStep {
+            step,
+            ok: result.is_ok(),
+            error: result.err().map(|e| format!("{:#}", e)),
+        });
+        self.ok = self.steps.iter().all(|s| s.ok);
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &LogoutStep> {
+        self.steps.iter().filter(|s| !s.ok)
+    }
+}
+
+/// 登录的账号与本地数据所属账号不同，需要先清除上一账号的数据。
+/// 没有记录所属账号（首次登录或已忘记账号）时不清除。
+pub fn account_changed(owner: Option<&str>, username: &str) -> bool {
+    owner.is_some_and(|o| !o.trim().eq_ignore_ascii_case(username.trim()))
+}
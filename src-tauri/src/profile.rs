@@ -0,0 +1,200 @@
+use anyhow::{bail, Context, Result};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::SignInfo;
+use crate::security::{decrypt_data, encrypt_data};
+
+const PROFILES_STORE: &str = "sign_profiles.json";
+
+/// 串行化对 `sign_profiles.json` 的读改写（保存、删除 profile 与设置备注可能并发执行）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 一份可复用的报名信息（`needSignInfo == "1"` 的活动需要）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignProfile {
+    /// 新建时留空，由 `save_profile` 生成
+    #[serde(default)]
+    pub id: String,
+    /// 便于区分的名称，如「默认」「社团用」
+    pub name: String,
+    pub info: SignInfo,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// 存储内容：整体序列化后用 machine_uid 派生的密钥加密，与密码一致。
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileData {
+    profiles: Vec<SignProfile>,
+    /// 活动 ID -> 该活动的报名备注
+    remarks: HashMap<String, String>,
+}
+
+impl ProfileData {
+    /// 按 ID 取 profile；`id` 为空时取默认项。
+    fn profile(&self, id: Option<&str>) -> Result<Option<&SignProfile>> {
+        Ok(match id {
+            Some(id) => Some(
+                self.profiles
+                    .iter()
+                    .find(|p| p.id == id)
+                    .with_context(|| format!("Sign profile {} not found", id))?,
+            ),
+            None => self.profiles.iter().find(|p| p.is_default),
+        })
+    }
+}
+
+fn load(app: &AppHandle) -> Result<ProfileData> {
+    let store = app
+        .store(PROFILES_STORE)
+        .context("Failed to access store")?;
+    let encrypted = match store
+        .get("data")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+    {
+        Some(e) => e,
+        None => return Ok(ProfileData::default()),
+    };
+    let plain = decrypt_data(app, &encrypted)?;
+    serde_json::from_str(&plain).context("Corrupted sign profile store")
+}
+
+fn save(app: &AppHandle, data: &ProfileData) -> Result<()> {
+    let store = app
+        .store(PROFILES_STORE)
+        .context("Failed to access store")?;
+    let encrypted = encrypt_data(app, &serde_json::to_string(data)?)?;
+    store.set("data", json!(encrypted));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 在写锁内读取（解密）存储、修改并写回。
+fn modify<T>(app: &AppHandle, f: impl FnOnce(&mut ProfileData) -> T) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut data = load(app)?;
+    let out = f(&mut data);
+    save(app, &data)?;
+    Ok(out)
+}
+
+fn new_id() -> String {
+    let mut bytes = [0u8; 8];
+    thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub fn list_profiles(app: &AppHandle) -> Result<Vec<SignProfile>> {
+    Ok(load(app)?.profiles)
+}
+
+/// 新建或更新（按 `id`）一份报名信息，保存前规范化并校验；校验失败为 `INVALID_SIGN_INFO`，
+/// `fields` 为逐字段错误。第一份或标记为默认的 profile 会成为唯一的默认项。
+pub fn save_profile(app: &AppHandle, mut profile: SignProfile) -> Result<SignProfile> {
+    profile.info = profile.info.normalized();
+    let errors = profile.info.validate();
+    if !errors.is_empty() {
+        bail!(
+            AppError::rule("INVALID_SIGN_INFO", "Sign info validation failed")
+                .with_detail(json!({ "fields": errors }))
+        );
+    }
+    if profile.id.is_empty() {
+        profile.id = new_id();
+    }
+
+    modify(app, |data| {
+        if data.profiles.is_empty() {
+            profile.is_default = true;
+        }
+        if profile.is_default {
+            for p in &mut data.profiles {
+                p.is_default = false;
+            }
+        }
+        match data.profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile.clone(),
+            None => data.profiles.push(profile.clone()),
+        }
+        profile
+    })
+}
+
+/// 删除后若没有默认项，则把第一份设为默认。
+pub fn delete_profile(app: &AppHandle, id: &str) -> Result<()> {
+    modify(app, |data| {
+        data.profiles.retain(|p| p.id != id);
+        if !data.profiles.iter().any(|p| p.is_default) {
+            if let Some(first) = data.profiles.first_mut() {
+                first.is_default = true;
+            }
+        }
+    })
+}
+
+/// 按 ID 取 profile；`id` 为空时取默认项。都没有时返回 `None`。
+pub fn get_profile(app: &AppHandle, id: Option<&str>) -> Result<Option<SignProfile>> {
+    Ok(load(app)?.profile(id)?.cloned())
+}
+
+pub fn get_activity_remarks(app: &AppHandle, activity_id: &str) -> Result<Option<String>> {
+    Ok(load(app)?.remarks.remove(activity_id))
+}
+
+/// 设置某活动的报名备注；空字符串表示删除。
+pub fn set_activity_remarks(app: &AppHandle, activity_id: &str, remarks: &str) -> Result<()> {
+    modify(app, |data| {
+        if remarks.trim().is_empty() {
+            data.remarks.remove(activity_id);
+        } else {
+            data.remarks
+                .insert(activity_id.to_string(), remarks.trim().to_string());
+        }
+    })
+}
+
+/// 组装报名时提交的 SignInfo：profile（默认或指定）+ 备注。
+/// 备注优先级：调用方传入 > 该活动保存的备注 > profile 自带备注。
+/// 没有任何 profile 时返回 `None`，由 `SecondClass::apply` 回退到 `SignInfo::get_self`。
+pub fn resolve_sign_info(
+    app: &AppHandle,
+    activity_id: &str,
+    profile_id: Option<&str>,
+    remarks: Option<&str>,
+) -> Result<Option<SignInfo>> {
+    // 只解密一次存储
+    let data = load(app)?;
+    let mut info = match data.profile(profile_id)? {
+        Some(p) => p.info.clone(),
+        None => return Ok(None),
+    };
+    if let Some(r) = remarks {
+        info.remarks = r.trim().to_string();
+    } else if let Some(r) = data.remarks.get(activity_id) {
+        info.remarks = r.clone();
+    }
+    let errors = info.validate();
+    if let Some(e) = errors.first() {
+        bail!("Invalid sign info: {}: {}", e.field, e.message);
+    }
+    Ok(Some(info))
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app
+        .store(PROFILES_STORE)
+        .context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
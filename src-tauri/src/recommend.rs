@@ -1,19 +1,30 @@
+use crate::rustustc::young::place::{self, is_weekday_evening};
 use crate::rustustc::young::service::YouthService;
-use crate::rustustc::young::{SCFilter, SecondClass};
+use crate::rustustc::young::{Campus, CampusDictionary, SCFilter, SecondClass};
 use ammonia::Builder;
 use anyhow::Result;
 use jieba_rs::Jieba;
 use once_cell::sync::Lazy;
 use std::collections::{HashMap, HashSet};
 
-static JIEBA: Lazy<Jieba> = Lazy::new(|| Jieba::new());
-static EMPTY_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| HashSet::new());
+static JIEBA: Lazy<Jieba> = Lazy::new(Jieba::new);
+static EMPTY_SET: Lazy<HashSet<&'static str>> = Lazy::new(HashSet::new);
 
 pub struct Recommender;
 
 impl Recommender {
     /// Core recommendation function
     pub async fn recommend(service: &YouthService, limit: usize) -> Result<Vec<SecondClass>> {
+        Self::recommend_near(service, limit, None, CampusDictionary::default_ref()).await
+    }
+
+    /// 同 `recommend`，`home_campus` 为用户常驻校区，其他校区的活动会被降权。
+    pub async fn recommend_near(
+        service: &YouthService,
+        limit: usize,
+        home_campus: Option<Campus>,
+        dict: &CampusDictionary,
+    ) -> Result<Vec<SecondClass>> {
         // 1. Fetch Data
         let history = SecondClass::get_participated(service).await?;
 
@@ -96,6 +107,10 @@ impl Recommender {
                     }
                 }
 
+                if let Some(home) = home_campus {
+                    score -= Self::campus_penalty(&item, home, dict);
+                }
+
                 // Because of popularity have to use SecondClass::update to get data otherwize zero
                 // so deprecated popularity based scoring
                 // let popularity = item.apply_num.unwrap_or(0) as f64;
@@ -124,21 +139,40 @@ impl Recommender {
         Ok(result)
     }
 
+    /// 跨校区降权：工作日晚上开始的场次基本无法赶到，其余时间略微降权。
+    /// 无法识别校区或包含常驻校区的活动不降权。
+    pub fn campus_penalty(item: &SecondClass, home: Campus, dict: &CampusDictionary) -> f64 {
+        let places = place::places(item, dict);
+        let campuses: Vec<Campus> = places.iter().filter_map(|p| p.campus).collect();
+        if campuses.is_empty() || campuses.contains(&home) {
+            return 0.0;
+        }
+        let mut starts: Vec<_> = places.iter().filter_map(|p| p.start).collect();
+        if starts.is_empty() {
+            starts.extend(item.hold_time().ok().map(|t| t.start));
+        }
+        if starts.iter().any(|t| is_weekday_evening(*t)) {
+            5.0
+        } else {
+            1.0
+        }
+    }
+
     fn extract_tokens(item: &SecondClass) -> Vec<String> {
         let mut text = item.name.clone();
 
         if let Some(dept) = item.department() {
-            text.push_str(" ");
+            text.push(' ');
             text.push_str(&dept.name);
         }
 
         if let Some(conceive) = &item.conceive {
-            text.push_str(" ");
+            text.push(' ');
             text.push_str(&Self::strip_html(conceive));
         }
 
         if let Some(content) = &item.base_content {
-            text.push_str(" ");
+            text.push(' ');
             text.push_str(&Self::strip_html(content));
         }
 
@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use chrono::Duration as ChronoDuration;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::rustustc::young::model::now;
+use crate::rustustc::young::reminder::{self, Reminder, ReminderSettings};
+use crate::rustustc::young::{SecondClass, YouthService};
+use crate::settings;
+use crate::state::AppState;
+
+const REMINDERS_STORE: &str = "reminders.json";
+pub const SETTINGS_KEY: &str = "reminders";
+/// 检查到期提醒的间隔
+const TICK: Duration = Duration::from_secs(30);
+/// 错过超过该时长的提醒（如应用未运行）不再发送
+const STALE_MINUTES: i64 = 30;
+/// 最多保留的已发送 key 数
+const MAX_FIRED: usize = 500;
+
+/// 串行化对 `reminders.json` 的读改写（定时发送、变更检测与报名操作后的重排会同时写入）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn pending(app: &AppHandle) -> Result<Vec<Reminder>> {
+    let store = app
+        .store(REMINDERS_STORE)
+        .context("Failed to access store")?;
+    Ok(store
+        .get("pending")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn fired(app: &AppHandle) -> Result<Vec<String>> {
+    let store = app
+        .store(REMINDERS_STORE)
+        .context("Failed to access store")?;
+    Ok(store
+        .get("fired")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save(app: &AppHandle, pending: &[Reminder], fired: &[String]) -> Result<()> {
+    let store = app
+        .store(REMINDERS_STORE)
+        .context("Failed to access store")?;
+    store.set("pending", json!(pending));
+    store.set("fired", json!(fired));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 在写锁内读取待发送提醒与已发送 key、修改并写回。
+fn modify<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Vec<Reminder>, &mut Vec<String>) -> T,
+) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut pending = pending(app)?;
+    let mut fired = fired(app)?;
+    let out = f(&mut pending, &mut fired);
+    save(app, &pending, &fired)?;
+    Ok(out)
+}
+
+/// 按已报名活动重新生成待发送的提醒。
+pub fn reschedule(app: &AppHandle, registered: &[SecondClass]) -> Result<Vec<Reminder>> {
+    let settings: ReminderSettings = settings::load(app, SETTINGS_KEY)?;
+    modify(app, |pending, fired| {
+        *pending = reminder::plan(registered, &settings, fired, now());
+        pending.clone()
+    })
+}
+
+/// 拉取已报名活动后重新生成提醒。
+pub async fn resync(app: &AppHandle, service: &YouthService) -> Result<Vec<Reminder>> {
+    let now = now();
+    let registered: Vec<SecondClass> = SecondClass::get_participated(service)
+        .await?
+        .into_iter()
+        .filter(|sc| crate::is_still_registered(sc, now))
+        .collect();
+    reschedule(app, &registered)
+}
+
+/// 报名、取消等操作后在后台重新生成提醒，不阻塞调用方。
+pub fn resync_in_background(app: &AppHandle, service: Arc<YouthService>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = resync(&app, &service).await;
+    });
+}
+
+/// 发送到期的提醒，过期太久的静默丢弃。
+fn fire_due(app: &AppHandle) -> Result<()> {
+    let now = now();
+    if pending(app)?.iter().all(|r| r.fire_at > now) {
+        return Ok(());
+    }
+    // 先在锁内移出到期提醒并记为已发送，再发通知，避免与重排交错时重复发送或丢失
+    let due = modify(app, |pending, fired| {
+        let (due, rest): (Vec<Reminder>, Vec<Reminder>) =
+            pending.drain(..).partition(|r| r.fire_at <= now);
+        *pending = rest;
+        fired.extend(due.iter().map(|r| r.key()));
+        let overflow = fired.len().saturating_sub(MAX_FIRED);
+        fired.drain(..overflow);
+        due
+    })?;
+    for r in due
+        .iter()
+        .filter(|r| now - r.fire_at <= ChronoDuration::minutes(STALE_MINUTES))
+    {
+        let _ = app
+            .notification()
+            .builder()
+            .title("活动提醒")
+            .body(r.message())
+            .show();
+    }
+    Ok(())
+}
+
+/// 定时发送到期提醒；每次登录后先同步一次，其余由变更检测与报名操作触发重排。
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut synced = false;
+        loop {
+            let _ = fire_due(&app);
+            tokio::time::sleep(TICK).await;
+            let service = app.state::<AppState>().session.current_service();
+            match service {
+                Some(service) if !synced => {
+                    synced = resync(&app, &service).await.is_ok();
+                }
+                Some(_) => {}
+                None => synced = false,
+            }
+        }
+    });
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app
+        .store(REMINDERS_STORE)
+        .context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
@@ -14,6 +14,7 @@ use serde_json::Value;
 use reqwest_cookie_store::CookieStoreMutex;
 
 use crate::rustustc::cas::info::UserInfo;
+use crate::rustustc::error::CasError;
 use crate::rustustc::url::generate_url;
 
 pub struct CASClient {
@@ -21,6 +22,12 @@ pub struct CASClient {
     pub cookie_store: Arc<CookieStoreMutex>,
 }
 
+impl Default for CASClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CASClient {
     pub fn new() -> Self {
         let cookie_store = Arc::new(CookieStoreMutex::default());
@@ -130,7 +137,7 @@ impl CASClient {
                 r#"<div\s+class="alert alert-danger"\s+id="login-error-msg">\s*<span>([^<]+)</span>\s*</div>"#,
             )?;
             if let Some(caps) = re_err.captures(&text) {
-                bail!("Login failed: {}", &caps[1]);
+                return Err(CasError(caps[1].trim().to_string()).into());
             }
             let debug_text = if text.len() > 200 {
                 &text[..200]
@@ -146,26 +153,34 @@ impl CASClient {
     }
 
     pub async fn is_login(&self) -> bool {
+        self.check_login().await.unwrap_or(false)
+    }
+
+    /// 同 `is_login`，但区分网络错误与未登录。
+    pub async fn check_login(&self) -> Result<bool> {
         let url = generate_url("id", "cas/login");
-        let res = self.client.get(&url).send().await;
-        match res {
-            Ok(resp) => {
-                let final_url = resp.url().as_str();
-                !final_url.contains("cas/login")
-            }
-            Err(_) => false,
-        }
+        let resp = self.client.get(&url).send().await?;
+        Ok(!resp.url().as_str().contains("cas/login"))
     }
 
     pub async fn logout(&self) -> Result<()> {
         let logout_url = generate_url("id", "gate/logout");
         let res = self.client.get(&logout_url).send().await;
-        match res {
+        match res.and_then(|resp| resp.error_for_status()) {
             Ok(_) => Ok(()),
             Err(e) => Err(anyhow::anyhow!("Logout failed: {}", e)),
         }
     }
 
+    /// 清空 Cookie（登出后不再携带 CAS 会话）。
+    pub fn clear_cookies(&self) -> Result<()> {
+        self.cookie_store
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Cookie store is poisoned"))?
+            .clear();
+        Ok(())
+    }
+
     pub async fn get_info(&self) -> Result<UserInfo> {
         let user_url = generate_url("id", "gate/getUser");
 
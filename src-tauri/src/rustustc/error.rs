@@ -0,0 +1,216 @@
+use crate::rustustc::young::QueryError;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::fmt;
+use tauri_plugin_http::reqwest;
+
+// ==================== 错误类型 (Error) ====================
+//
+// rustustc 内部仍使用 anyhow，关键位置抛出带类型的 `YouthApiError` / `CasError`；
+// 命令层通过 `AppError::from(anyhow::Error)` 沿错误链归类，序列化为
+// `{code, message, retryable, ...}` 交给前端。错误码见 docs/backend.md。
+
+/// 二课接口返回 `success=false`，`message` 为接口原文。
+#[derive(Debug, Clone, PartialEq)]
+pub struct YouthApiError {
+    pub code: Option<i64>,
+    pub message: String,
+}
+
+impl YouthApiError {
+    pub fn from_response(resp: &Value) -> Self {
+        Self {
+            code: resp["code"].as_i64(),
+            message: resp["message"].as_str().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// token 失效（Jeecg 返回 401 或提示重新登录）。
+    pub fn is_session_expired(&self) -> bool {
+        self.code == Some(401)
+            || ["Token失效", "token失效", "重新登录"]
+                .iter()
+                .any(|k| self.message.contains(k))
+    }
+}
+
+impl fmt::Display for YouthApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "API Error: {}", self.message)
+    }
+}
+
+impl std::error::Error for YouthApiError {}
+
+/// 统一身份认证拒绝登录（密码错误、需要验证码等），内容为页面提示原文。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CasError(pub String);
+
+impl fmt::Display for CasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Login failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for CasError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// 尚未登录
+    AuthRequired,
+    /// 会话失效，需要刷新或重新登录
+    SessionExpired(String),
+    Cas(String),
+    Network(String),
+    Parse(String),
+    /// 二课接口拒绝，内容为接口原文
+    Youth(String),
+    ActivityFull(String),
+    TimeConflict(String),
+    /// 本地校验或业务规则不满足，`detail` 的字段并入序列化结果
+    Rule {
+        code: &'static str,
+        message: String,
+        detail: Option<Value>,
+    },
+    Internal(String),
+}
+
+impl AppError {
+    pub fn rule(code: &'static str, message: impl ToString) -> Self {
+        Self::Rule {
+            code,
+            message: message.to_string(),
+            detail: None,
+        }
+    }
+
+    pub fn with_detail(self, detail: Value) -> Self {
+        match self {
+            Self::Rule { code, message, .. } => Self::Rule {
+                code,
+                message,
+                detail: Some(detail),
+            },
+            other => other,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AuthRequired => "AUTH_REQUIRED",
+            Self::SessionExpired(_) => "SESSION_EXPIRED",
+            Self::Cas(_) => "CAS_ERROR",
+            Self::Network(_) => "NETWORK_ERROR",
+            Self::Parse(_) => "PARSE_ERROR",
+            Self::Youth(_) => "YOUTH_API_ERROR",
+            Self::ActivityFull(_) => "ACTIVITY_FULL",
+            Self::TimeConflict(_) => "TIME_CONFLICT",
+            Self::Rule { code, .. } => code,
+            Self::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::AuthRequired => "Please login first",
+            Self::SessionExpired(m)
+            | Self::Cas(m)
+            | Self::Network(m)
+            | Self::Parse(m)
+            | Self::Youth(m)
+            | Self::ActivityFull(m)
+            | Self::TimeConflict(m)
+            | Self::Internal(m) => m,
+            Self::Rule { message, .. } => message,
+        }
+    }
+
+    /// 原样重试可能成功（网络问题）。会话失效需先刷新，不算可重试。
+    pub fn retryable(&self) -> bool {
+        matches!(self, Self::Network(_))
+    }
+
+    fn from_youth(e: &YouthApiError) -> Self {
+        let m = e.message.clone();
+        if e.is_session_expired() {
+            Self::SessionExpired(m)
+        } else if m.contains("已满") || m.contains("名额") {
+            Self::ActivityFull(m)
+        } else if m.contains("时间冲突") {
+            Self::TimeConflict(m)
+        } else {
+            Self::Youth(m)
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", self.message())?;
+        map.serialize_entry("retryable", &self.retryable())?;
+        if let Self::Rule {
+            detail: Some(Value::Object(detail)),
+            ..
+        } = self
+        {
+            for (k, v) in detail {
+                if !matches!(k.as_str(), "code" | "message" | "retryable") {
+                    map.serialize_entry(k, v)?;
+                }
+            }
+        }
+        map.end()
+    }
+}
+
+/// 沿错误链找到第一个可识别的错误；都不认识时为 `Internal`，保留完整上下文。
+impl From<anyhow::Error> for AppError {
+    fn from(e: anyhow::Error) -> Self {
+        for cause in e.chain() {
+            if let Some(app) = cause.downcast_ref::<AppError>() {
+                return app.clone();
+            }
+            if let Some(youth) = cause.downcast_ref::<YouthApiError>() {
+                return Self::from_youth(youth);
+            }
+            if let Some(cas) = cause.downcast_ref::<CasError>() {
+                return Self::Cas(cas.0.clone());
+            }
+            if let Some(req) = cause.downcast_ref::<reqwest::Error>() {
+                return if req.is_decode() {
+                    Self::Parse(format!("{:#}", e))
+                } else {
+                    Self::Network(format!("{:#}", e))
+                };
+            }
+            if cause.is::<serde_json::Error>() || cause.is::<chrono::ParseError>() {
+                return Self::Parse(format!("{:#}", e));
+            }
+        }
+        Self::Internal(format!("{:#}", e))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<QueryError> for AppError {
+    fn from(e: QueryError) -> Self {
+        Self::rule("QUERY_SYNTAX", &e.message).with_detail(json!({ "span": e.span }))
+    }
+}
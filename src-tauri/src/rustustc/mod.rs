@@ -1,3 +1,4 @@
 pub mod cas;
+pub mod error;
 pub mod url;
 pub mod young;
@@ -0,0 +1,184 @@
+use crate::rustustc::young::model::{CstDateTime, SecondClass, Status, TimePeriod};
+use crate::rustustc::young::service::YouthService;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// ==================== 学时申诉 (Appeal) ====================
+//
+// 学时公示期内或被驳回后，可对活动提交申诉（文字理由 + 可选佐证附件）。
+//
+// 未经验证：`item/scItemAppeal/*` 三个接口路径与字段名（`appealReason`、`examineStatus`、
+// `attachment`）按本项目其他 Jeecg 接口（如 `item/scItem/*`）的命名推测，尚未用真实账号抓包确认；
+// `sys/common/upload` 为 Jeecg 通用上传接口。接口不符时请求会返回 `YOUTH_API_ERROR`。
+
+const LIST_URL: &str = "item/scItemAppeal/list";
+const DETAIL_URL: &str = "item/scItemAppeal/queryById";
+const SUBMIT_URL: &str = "item/scItemAppeal/add";
+const UPLOAD_URL: &str = "sys/common/upload";
+
+/// 申诉理由最大字数
+pub const MAX_REASON: usize = 500;
+/// 附件最多个数
+pub const MAX_ATTACHMENTS: usize = 3;
+/// 单个附件最大字节数
+pub const MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AppealStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Unknown,
+}
+
+impl AppealStatus {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => AppealStatus::Pending,
+            1 => AppealStatus::Approved,
+            2 => AppealStatus::Rejected,
+            _ => AppealStatus::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Appeal {
+    pub id: String,
+    #[serde(alias = "itemId")]
+    pub item_id: Option<String>,
+    #[serde(alias = "itemName")]
+    pub item_name: Option<String>,
+    #[serde(alias = "appealReason")]
+    pub reason: Option<String>,
+    #[serde(alias = "examineStatus")]
+    pub status_code: Option<i32>,
+    #[serde(alias = "createTime")]
+    pub create_time_str: Option<String>,
+
+    #[serde(flatten)]
+    pub raw: Value,
+}
+
+impl Appeal {
+    pub fn status(&self) -> AppealStatus {
+        self.status_code
+            .map(AppealStatus::from_code)
+            .unwrap_or(AppealStatus::Unknown)
+    }
+
+    pub fn create_time(&self) -> Result<CstDateTime> {
+        TimePeriod::parse(self.create_time_str.as_deref().unwrap_or(""))
+    }
+
+    /// 审核意见（驳回原因）。
+    pub fn opinion(&self) -> Option<String> {
+        ["examineOpinion", "auditOpinion", "rejectReason"]
+            .iter()
+            .find_map(|&key| self.raw.get(key).and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    /// 附件存储路径（接口以逗号分隔）。
+    pub fn attachments(&self) -> Vec<String> {
+        self.raw
+            .get("attachment")
+            .and_then(|v| v.as_str())
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub async fn list(service: &YouthService) -> Result<Vec<Appeal>> {
+        let raw = service.page_search(LIST_URL, json!({}), -1, 20).await?;
+        let list = raw
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(list)
+    }
+
+    pub async fn get_by_id(service: &YouthService, id: &str) -> Result<Appeal> {
+        let data = service
+            .get_result(DETAIL_URL, Some(json!({ "id": id })))
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// 校验申诉理由与附件，返回规范化后的理由。
+    pub fn validate(reason: &str, attachments: &[(String, Vec<u8>)]) -> Result<String> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            bail!("Appeal reason is required");
+        }
+        if reason.chars().count() > MAX_REASON {
+            bail!("Appeal reason exceeds {} characters", MAX_REASON);
+        }
+        if attachments.len() > MAX_ATTACHMENTS {
+            bail!("At most {} attachments are allowed", MAX_ATTACHMENTS);
+        }
+        if let Some((name, _)) = attachments
+            .iter()
+            .find(|(_, bytes)| bytes.len() > MAX_ATTACHMENT_SIZE)
+        {
+            bail!("Attachment {} exceeds 10 MB", name);
+        }
+        Ok(reason.to_string())
+    }
+
+    /// 对活动提交申诉；附件为 (文件名, 内容)，先逐个上传再随申诉提交存储路径。
+    pub async fn submit(
+        service: &YouthService,
+        item: &SecondClass,
+        reason: &str,
+        attachments: Vec<(String, Vec<u8>)>,
+    ) -> Result<()> {
+        let reason = Self::validate(reason, &attachments)?;
+        if !appealable(item.status()) {
+            bail!(
+                "Activity status {} does not allow appeals",
+                item.status().text()
+            );
+        }
+
+        let mut paths = Vec::with_capacity(attachments.len());
+        for (name, bytes) in attachments {
+            paths.push(service.upload(UPLOAD_URL, &name, bytes).await?);
+        }
+
+        let body = json!({
+            "itemId": item.id,
+            "appealReason": reason,
+            "attachment": paths.join(","),
+        });
+        service
+            .request(SUBMIT_URL, "post", None, Some(body))
+            .await?;
+        Ok(())
+    }
+
+    /// 审核中的申诉记录。
+    pub fn pending(appeals: &[Appeal]) -> Vec<Appeal> {
+        appeals
+            .iter()
+            .filter(|a| a.status() == AppealStatus::Pending)
+            .cloned()
+            .collect()
+    }
+}
+
+/// 学时公示（含追加公示）期间或学时被驳回后可以申诉。
+pub fn appealable(status: Status) -> bool {
+    matches!(
+        status,
+        Status::HourPublic | Status::HourAppendPublic | Status::HourRejected
+    )
+}
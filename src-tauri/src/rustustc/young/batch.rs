@@ -0,0 +1,74 @@
+use crate::rustustc::young::model::{SecondClass, Status, TimePeriod};
+use serde::Serialize;
+
+// ==================== 批量报名 / 取消 (Batch) ====================
+//
+// 批量操作共用一次已报名列表与报名信息，逐个执行并返回每个活动的结果。
+// 批次内按传入顺序处理：时间重叠的活动只报名靠前的一个。
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum BatchOutcome {
+    Success,
+    /// 名额已满
+    Full,
+    /// 与已报名活动或批次内靠前的活动时间冲突，`with` 为冲突的活动 ID
+    Conflict {
+        with: Vec<String>,
+    },
+    /// 不在报名中，或（取消时）活动已开始
+    Closed,
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItem {
+    pub activity_id: String,
+    pub activity_name: String,
+    #[serde(flatten)]
+    pub outcome: BatchOutcome,
+}
+
+/// 按接口返回的提示归类失败原因。
+pub fn classify_error(message: &str) -> BatchOutcome {
+    if message.contains("已满") || message.contains("名额") {
+        BatchOutcome::Full
+    } else if message.contains("时间冲突") {
+        BatchOutcome::Conflict { with: Vec::new() }
+    } else if ["未开始", "已结束", "已截止", "不在报名"]
+        .iter()
+        .any(|k| message.contains(k))
+    {
+        BatchOutcome::Closed
+    } else {
+        BatchOutcome::Error {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// 报名前的本地检查，返回 `None` 表示可以报名。
+///
+/// `taken` 为已占用的时间段：已报名的活动与批次内已成功的活动。
+pub fn precheck(sc: &SecondClass, taken: &[(String, TimePeriod)]) -> Option<BatchOutcome> {
+    if sc.applied() {
+        return Some(BatchOutcome::Success);
+    }
+    if sc.status() != Status::Applying {
+        return Some(BatchOutcome::Closed);
+    }
+    if let (Some(num), Some(limit)) = (sc.apply_num, sc.apply_limit) {
+        if limit > 0 && num >= limit {
+            return Some(BatchOutcome::Full);
+        }
+    }
+    let ht = sc.hold_time().ok()?;
+    let with: Vec<String> = taken
+        .iter()
+        .filter(|(id, t)| *id != sc.id && t.is_overlap(&ht))
+        .map(|(id, _)| id.clone())
+        .collect();
+    (!with.is_empty()).then_some(BatchOutcome::Conflict { with })
+}
@@ -0,0 +1,209 @@
+use crate::rustustc::young::model::{CstDateTime, SecondClass, SHANGHAI};
+use crate::rustustc::young::service::YouthService;
+use anyhow::Result;
+use chrono::{Duration, TimeZone};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use url::Url;
+
+// ==================== 扫码签到 / 签退 (Checkin) ====================
+//
+// 现场二维码解码后的字符串由前端传入。支持两种载荷：
+// - URL：`...?itemId=<id>&type=1&code=<code>&t=<毫秒时间戳>`
+// - JSON：`{"itemId": "<id>", "type": "signOut", "code": "...", "t": 1765000000000}`
+// `type` 为 1 / signIn 表示签到，2 / signOut 表示签退。
+//
+// 未经验证：上述两种载荷格式（字段名 `itemId`/`type`/`code`/`t`、时间戳为毫秒）与提交接口
+// `mobile/item/signIn/{id}`、`mobile/item/signOut/{id}`（请求体 `{"code": ...}`）都是推测的，
+// 尚未对照真实现场二维码与抓包确认；接口不符时提交会返回 `YOUTH_API_ERROR`。
+
+/// 签到可提前的时间
+pub const SIGN_IN_EARLY_MINUTES: i64 = 30;
+/// 签退可延后的时间
+pub const SIGN_OUT_LATE_MINUTES: i64 = 60;
+/// 带时间戳的二维码有效期
+pub const QR_TTL_MINUTES: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    SignIn,
+    SignOut,
+}
+
+impl CheckKind {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "1" | "signin" | "sign_in" | "in" => Some(CheckKind::SignIn),
+            "2" | "signout" | "sign_out" | "signoff" | "out" => Some(CheckKind::SignOut),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self) -> &'static str {
+        match self {
+            CheckKind::SignIn => "签到",
+            CheckKind::SignOut => "签退",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum CheckinError {
+    Unrecognized,
+    MissingItemId,
+    UnknownKind,
+    WrongActivity { expected: String, actual: String },
+    NotRegistered,
+    Closed { kind: CheckKind },
+    NotOpenYet { opens_at: CstDateTime },
+    WindowClosed { closed_at: CstDateTime },
+    Expired,
+}
+
+impl fmt::Display for CheckinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckinError::Unrecognized => write!(f, "无法识别的二维码"),
+            CheckinError::MissingItemId => write!(f, "二维码中缺少活动 ID"),
+            CheckinError::UnknownKind => write!(f, "无法判断是签到还是签退"),
+            CheckinError::WrongActivity { .. } => write!(f, "二维码不属于该活动"),
+            CheckinError::NotRegistered => write!(f, "未报名该活动"),
+            CheckinError::Closed { kind } => write!(f, "该活动未开放扫码{}", kind.text()),
+            CheckinError::NotOpenYet { opens_at } => {
+                write!(f, "尚未开放，{} 起可用", opens_at.format("%m-%d %H:%M"))
+            }
+            CheckinError::WindowClosed { closed_at } => {
+                write!(f, "已于 {} 截止", closed_at.format("%m-%d %H:%M"))
+            }
+            CheckinError::Expired => write!(f, "二维码已过期，请重新扫描"),
+        }
+    }
+}
+
+impl std::error::Error for CheckinError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckinPayload {
+    pub item_id: String,
+    pub kind: CheckKind,
+    pub code: Option<String>,
+    /// 二维码生成时间（部分二维码带有，用于判断是否过期）
+    pub issued_at: Option<CstDateTime>,
+}
+
+fn value_str(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn from_millis(s: &str) -> Option<CstDateTime> {
+    let ms: i64 = s.trim().parse().ok()?;
+    SHANGHAI.timestamp_millis_opt(ms).single()
+}
+
+impl CheckinPayload {
+    pub fn parse(raw: &str) -> Result<Self, CheckinError> {
+        let raw = raw.trim();
+        let pairs: Vec<(String, String)> = if raw.starts_with('{') {
+            let v: Value = serde_json::from_str(raw).map_err(|_| CheckinError::Unrecognized)?;
+            v.as_object()
+                .ok_or(CheckinError::Unrecognized)?
+                .iter()
+                .filter_map(|(k, v)| Some((k.clone(), value_str(v)?)))
+                .collect()
+        } else {
+            let url = Url::parse(raw).map_err(|_| CheckinError::Unrecognized)?;
+            let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+            // 参数也可能在 hash 路由（`#/sign?itemId=...`）中
+            if let Some((_, query)) = url.fragment().and_then(|f| f.split_once('?')) {
+                pairs.extend(url::form_urlencoded::parse(query.as_bytes()).into_owned());
+            }
+            pairs
+        };
+        let get = |keys: &[&str]| {
+            keys.iter().find_map(|k| {
+                pairs
+                    .iter()
+                    .find(|(name, v)| name == k && !v.trim().is_empty())
+                    .map(|(_, v)| v.trim().to_string())
+            })
+        };
+
+        let item_id = get(&["itemId", "item_id", "id"]).ok_or(CheckinError::MissingItemId)?;
+        let kind = get(&["type", "signType", "kind"])
+            .and_then(|k| CheckKind::parse(&k))
+            .ok_or(CheckinError::UnknownKind)?;
+        Ok(Self {
+            item_id,
+            kind,
+            code: get(&["code", "signCode", "token"]),
+            issued_at: get(&["t", "timestamp"]).and_then(|t| from_millis(&t)),
+        })
+    }
+
+    /// 签到 / 签退允许的时间窗口。
+    pub fn window(&self, sc: &SecondClass) -> Option<(CstDateTime, CstDateTime)> {
+        let hold = sc.hold_time().ok()?;
+        Some(match self.kind {
+            CheckKind::SignIn => (
+                hold.start - Duration::minutes(SIGN_IN_EARLY_MINUTES),
+                hold.end,
+            ),
+            CheckKind::SignOut => (
+                hold.start,
+                hold.end + Duration::minutes(SIGN_OUT_LATE_MINUTES),
+            ),
+        })
+    }
+
+    /// 对照活动详情校验：活动 ID、是否报名、是否开放扫码、时间窗口与二维码时效。
+    pub fn validate(&self, sc: &SecondClass, now: CstDateTime) -> Result<(), CheckinError> {
+        if self.item_id != sc.id {
+            return Err(CheckinError::WrongActivity {
+                expected: sc.id.clone(),
+                actual: self.item_id.clone(),
+            });
+        }
+        if sc.boolean_registration == Some(0) {
+            return Err(CheckinError::NotRegistered);
+        }
+        let closed_key = match self.kind {
+            CheckKind::SignIn => "qrSigninClosed",
+            CheckKind::SignOut => "qrSignoffClosed",
+        };
+        if sc.raw.get(closed_key).and_then(value_str).as_deref() == Some("1") {
+            return Err(CheckinError::Closed { kind: self.kind });
+        }
+        if let Some((opens_at, closed_at)) = self.window(sc) {
+            if now < opens_at {
+                return Err(CheckinError::NotOpenYet { opens_at });
+            }
+            if now > closed_at {
+                return Err(CheckinError::WindowClosed { closed_at });
+            }
+        }
+        if let Some(issued) = self.issued_at {
+            if now - issued > Duration::minutes(QR_TTL_MINUTES) {
+                return Err(CheckinError::Expired);
+            }
+        }
+        Ok(())
+    }
+
+    /// 未经验证：签到 / 签退接口路径与请求体为推测，见模块说明。
+    pub async fn submit(&self, service: &YouthService) -> Result<()> {
+        let url = match self.kind {
+            CheckKind::SignIn => format!("mobile/item/signIn/{}", self.item_id),
+            CheckKind::SignOut => format!("mobile/item/signOut/{}", self.item_id),
+        };
+        let body = json!({ "code": self.code.clone().unwrap_or_default() });
+        service.request(&url, "post", None, Some(body)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+use crate::rustustc::young::model::{now, CstDateTime, SecondClass, SignInfo};
+use crate::rustustc::young::service::YouthService;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+// ==================== 时间冲突处理 (Conflict) ====================
+//
+// 报名遇到时间冲突时，先算出与目标活动重叠的已报名活动并按策略生成计划（可只预览），
+// 只取消策略允许的活动；取消后报名仍失败时，重新报名已取消的活动并如实报告结果。
+// 重新报名使用取消前记录的报名信息，没有记录时回退到 `SignInfo::get_self` 并在结果中注明。
+
+/// 用户指定的取消策略。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConflictPolicy {
+    /// 不允许取消的活动
+    pub protected_ids: BTreeSet<String>,
+    /// 最多取消几个活动，`None` 为不限
+    pub max_cancel: Option<usize>,
+    /// 只取消学时不高于目标活动的
+    pub only_lower_hours: bool,
+}
+
+/// 不取消的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepReason {
+    /// 已开始或已结束，取消无意义
+    Started,
+    Protected,
+    MoreHours,
+    OverLimit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ConflictAction {
+    Cancel,
+    Keep { reason: KeepReason },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictItem {
+    pub activity_id: String,
+    pub activity_name: String,
+    pub start: String,
+    pub end: String,
+    pub valid_hour: Option<f64>,
+    #[serde(flatten)]
+    pub action: ConflictAction,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictPlan {
+    pub activity_id: String,
+    pub conflicts: Vec<ConflictItem>,
+    /// 有冲突且都可取消
+    pub feasible: bool,
+    /// 不可行的原因
+    pub reason: Option<String>,
+}
+
+impl ConflictPlan {
+    /// 按开始时间排序列出与 `target` 重叠的已报名活动，并逐个决定是否取消。
+    pub fn build(
+        target: &SecondClass,
+        participated: &[SecondClass],
+        policy: &ConflictPolicy,
+        now: CstDateTime,
+    ) -> Result<Self> {
+        let target_time = target.hold_time()?;
+        let mut overlapping: Vec<(&SecondClass, CstDateTime, CstDateTime)> = participated
+            .iter()
+            .filter(|sc| sc.id != target.id)
+            .filter_map(|sc| {
+                let ht = sc.hold_time().ok()?;
+                ht.is_overlap(&target_time)
+                    .then_some((sc, ht.start, ht.end))
+            })
+            .collect();
+        overlapping.sort_by_key(|(_, start, _)| *start);
+
+        let target_hours = target.valid_hour.unwrap_or(0.0);
+        let mut cancels = 0;
+        let conflicts: Vec<ConflictItem> = overlapping
+            .into_iter()
+            .map(|(sc, start, end)| {
+                let reason = if start <= now {
+                    Some(KeepReason::Started)
+                } else if policy.protected_ids.contains(&sc.id) {
+                    Some(KeepReason::Protected)
+                } else if policy.only_lower_hours && sc.valid_hour.unwrap_or(0.0) > target_hours {
+                    Some(KeepReason::MoreHours)
+                } else if policy.max_cancel.is_some_and(|max| cancels >= max) {
+                    Some(KeepReason::OverLimit)
+                } else {
+                    cancels += 1;
+                    None
+                };
+                ConflictItem {
+                    activity_id: sc.id.clone(),
+                    activity_name: sc.name.clone(),
+                    start: start.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    end: end.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    valid_hour: sc.valid_hour,
+                    action: match reason {
+                        Some(reason) => ConflictAction::Keep { reason },
+                        None => ConflictAction::Cancel,
+                    },
+                }
+            })
+            .collect();
+        // 本地没有重叠时取消任何活动都无济于事，重发报名只会得到同样的冲突
+        let reason = if conflicts.is_empty() {
+            Some("No registered activity overlaps this one locally, so the conflict cannot be resolved by cancelling".to_string())
+        } else if conflicts.iter().any(|c| c.action != ConflictAction::Cancel) {
+            Some(
+                "Some conflicting activities cannot be cancelled under the given policy"
+                    .to_string(),
+            )
+        } else {
+            None
+        };
+        Ok(Self {
+            activity_id: target.id.clone(),
+            conflicts,
+            feasible: reason.is_none(),
+            reason,
+        })
+    }
+
+    pub fn to_cancel(&self) -> impl Iterator<Item = &ConflictItem> {
+        self.conflicts
+            .iter()
+            .filter(|c| c.action == ConflictAction::Cancel)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreFailure {
+    pub activity_id: String,
+    pub message: String,
+}
+
+/// 执行结果：取消了哪些、是否报名成功、回滚恢复了哪些。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResolveOutcome {
+    pub applied: bool,
+    pub cancelled: Vec<String>,
+    pub restored: Vec<String>,
+    /// 已恢复、但因取消前没有记录而使用默认报名信息（`SignInfo::get_self`）的活动
+    pub restored_with_default_info: Vec<String>,
+    pub restore_failed: Vec<RestoreFailure>,
+    pub error: Option<String>,
+}
+
+impl ResolveOutcome {
+    pub fn summary(&self) -> String {
+        let mut msg = self.error.clone().unwrap_or_default();
+        if !self.restored.is_empty() {
+            msg += &format!(
+                "; restored {} cancelled registration(s)",
+                self.restored.len()
+            );
+        }
+        if !self.restored_with_default_info.is_empty() {
+            msg += &format!(
+                "; restored with default sign-up info: {}",
+                self.restored_with_default_info.join(", ")
+            );
+        }
+        if !self.restore_failed.is_empty() {
+            let ids: Vec<&str> = self
+                .restore_failed
+                .iter()
+                .map(|f| f.activity_id.as_str())
+                .collect();
+            msg += &format!("; failed to restore: {}", ids.join(", "));
+        }
+        msg
+    }
+}
+
+/// `resolve` 用到的报名操作；`YouthService` 为真实实现，测试中可替换。
+#[async_trait::async_trait]
+pub trait Registrar: Sync {
+    async fn cancel(&self, activity_id: &str) -> Result<()>;
+    async fn apply(
+        &self,
+        target: &SecondClass,
+        force: bool,
+        sign_info: Option<SignInfo>,
+    ) -> Result<bool>;
+    /// 重新报名已取消的活动；返回是否因没有记录而使用了默认报名信息。
+    async fn reapply(&self, activity_id: &str, sign_info: Option<SignInfo>) -> Result<bool>;
+}
+
+#[async_trait::async_trait]
+impl Registrar for YouthService {
+    async fn cancel(&self, activity_id: &str) -> Result<()> {
+        let sc = SecondClass::get_by_id(self, activity_id).await?;
+        sc.cancel_apply(self).await?;
+        Ok(())
+    }
+
+    async fn apply(
+        &self,
+        target: &SecondClass,
+        force: bool,
+        sign_info: Option<SignInfo>,
+    ) -> Result<bool> {
+        target.apply(self, force, false, sign_info, |_| None).await
+    }
+
+    async fn reapply(&self, activity_id: &str, sign_info: Option<SignInfo>) -> Result<bool> {
+        let sc = SecondClass::get_by_id(self, activity_id).await?;
+        let default_info = sc.need_sign_info() && sign_info.is_none();
+        if !sc.apply(self, true, false, sign_info, |_| None).await? {
+            bail!("Activity is not applyable");
+        }
+        Ok(default_info)
+    }
+}
+
+/// 按计划取消冲突活动后报名；任何一步失败都会尝试重新报名已取消的活动。
+///
+/// `recorded` 为取消前记录的各活动报名信息，恢复时使用。
+/// 计划不可行（没有冲突或有冲突不能取消）时直接报错，不做任何修改。
+pub async fn resolve<R: Registrar + ?Sized>(
+    ops: &R,
+    target: &SecondClass,
+    plan: &ConflictPlan,
+    force: bool,
+    sign_info: Option<SignInfo>,
+    recorded: &HashMap<String, SignInfo>,
+) -> Result<ResolveOutcome> {
+    if let Some(reason) = &plan.reason {
+        bail!("{}", reason);
+    }
+    let mut outcome = ResolveOutcome::default();
+    for item in plan.to_cancel() {
+        match ops.cancel(&item.activity_id).await {
+            Ok(()) => outcome.cancelled.push(item.activity_id.clone()),
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+
+    if outcome.error.is_none() {
+        match ops.apply(target, force, sign_info).await {
+            Ok(true) => outcome.applied = true,
+            Ok(false) => outcome.error = Some("Activity is not applyable".into()),
+            Err(e) => outcome.error = Some(e.to_string()),
+        }
+    }
+
+    if !outcome.applied {
+        for id in outcome.cancelled.clone() {
+            match ops.reapply(&id, recorded.get(&id).cloned()).await {
+                Ok(default_info) => {
+                    if default_info {
+                        outcome.restored_with_default_info.push(id.clone());
+                    }
+                    outcome.restored.push(id);
+                }
+                Err(e) => outcome.restore_failed.push(RestoreFailure {
+                    activity_id: id,
+                    message: e.to_string(),
+                }),
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+/// 先生成计划再执行；`dry_run` 时只返回计划。
+///
+/// `record` 在取消前为每个待取消的活动给出报名信息（如按保存的 profile 与备注重建），
+/// 返回 `None` 时恢复使用默认报名信息。
+pub async fn plan_and_resolve(
+    service: &YouthService,
+    target: &SecondClass,
+    policy: &ConflictPolicy,
+    force: bool,
+    sign_info: Option<SignInfo>,
+    dry_run: bool,
+    record: impl Fn(&str) -> Option<SignInfo> + Send,
+) -> Result<(ConflictPlan, Option<ResolveOutcome>)> {
+    let participated = SecondClass::get_participated(service).await?;
+    let plan = ConflictPlan::build(target, &participated, policy, now())?;
+    if dry_run || !plan.feasible {
+        return Ok((plan, None));
+    }
+    let recorded: HashMap<String, SignInfo> = plan
+        .to_cancel()
+        .filter_map(|c| record(&c.activity_id).map(|info| (c.activity_id.clone(), info)))
+        .collect();
+    let outcome = resolve(service, target, &plan, force, sign_info, &recorded).await?;
+    Ok((plan, Some(outcome)))
+}
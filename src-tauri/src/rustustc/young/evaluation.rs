@@ -0,0 +1,253 @@
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::{raw_flag, CstDateTime, SecondClass};
+use crate::rustustc::young::service::YouthService;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// ==================== 活动评价 (Evaluation) ====================
+//
+// 活动结束后，开启评价（`evaluation == "1"`）且未评价（`evaluated != 1`）的活动需要填写评价表。
+// 题型：星级评分、单选/多选、文本。
+//
+// 未经验证：`item/scItemEvaluation/getForm|add` 两个接口路径、题型编码（1 星级、2 单选、3 多选、
+// 4 文本）与字段名均按本项目其他 Jeecg 接口的命名推测，尚未用真实账号抓包确认。表单中出现无法
+// 识别的题目时 `fetch` 直接报错（`PARSE_ERROR`，消息中带原始题型），不会静默丢题后提交不完整的评价。
+
+const FORM_URL: &str = "item/scItemEvaluation/getForm";
+const SUBMIT_URL: &str = "item/scItemEvaluation/add";
+
+/// 星级评分缺省满分
+const DEFAULT_MAX_RATING: u8 = 5;
+/// 文本题缺省最大字数
+const DEFAULT_MAX_TEXT: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionOption {
+    pub value: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuestionKind {
+    Rating { max: u8 },
+    Single { options: Vec<QuestionOption> },
+    Multiple { options: Vec<QuestionOption> },
+    Text { max_len: usize },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Question {
+    pub id: String,
+    pub title: String,
+    pub required: bool,
+    pub kind: QuestionKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum AnswerValue {
+    Rating(u8),
+    Choice(Vec<String>),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Answer {
+    pub question_id: String,
+    pub value: AnswerValue,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnswerError {
+    pub question_id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationForm {
+    pub item_id: String,
+    pub questions: Vec<Question>,
+}
+
+fn str_field(v: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|&k| match v.get(k)? {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+impl Question {
+    /// 解析接口返回的题目。题型 `type`：1 星级、2 单选、3 多选、4 文本；缺少 ID 或题型未知时报错。
+    pub fn from_raw(v: &Value) -> Result<Self> {
+        let Some(id) = str_field(v, &["id", "questionId"]) else {
+            bail!(AppError::Parse(format!(
+                "Evaluation question without id: {}",
+                v
+            )));
+        };
+        let title = str_field(v, &["title", "questionName", "content"]).unwrap_or_default();
+        let options = || -> Vec<QuestionOption> {
+            v.get("options")
+                .and_then(|o| o.as_array())
+                .map(|list| {
+                    list.iter()
+                        .filter_map(|o| {
+                            let value = str_field(o, &["value", "id"])?;
+                            let text = str_field(o, &["text", "label", "content"])
+                                .unwrap_or(value.clone());
+                            Some(QuestionOption { value, text })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let raw_type = str_field(v, &["type", "questionType"]).unwrap_or_default();
+        let kind = match raw_type.as_str() {
+            "1" | "rating" => QuestionKind::Rating {
+                max: str_field(v, &["maxScore", "max"])
+                    .and_then(|m| m.parse().ok())
+                    .filter(|m| *m > 0)
+                    .unwrap_or(DEFAULT_MAX_RATING),
+            },
+            "2" | "single" => QuestionKind::Single { options: options() },
+            "3" | "multiple" => QuestionKind::Multiple { options: options() },
+            "4" | "text" => QuestionKind::Text {
+                max_len: str_field(v, &["maxLength"])
+                    .and_then(|m| m.parse().ok())
+                    .unwrap_or(DEFAULT_MAX_TEXT),
+            },
+            _ => bail!(AppError::Parse(format!(
+                "Unknown evaluation question type {:?} for question {}",
+                raw_type, id
+            ))),
+        };
+        Ok(Self {
+            id,
+            title,
+            required: raw_flag(v.get("required").or_else(|| v.get("isRequired"))),
+            kind,
+        })
+    }
+
+    fn check(&self, answer: Option<&AnswerValue>) -> Option<String> {
+        let answer = match answer {
+            None => return self.required.then(|| "必填".to_string()),
+            Some(a) => a,
+        };
+        let known = |options: &[QuestionOption], v: &str| options.iter().any(|o| o.value == v);
+        match (&self.kind, answer) {
+            (QuestionKind::Rating { max }, AnswerValue::Rating(r)) => {
+                (*r < 1 || r > max).then(|| format!("评分应在 1 到 {} 之间", max))
+            }
+            (QuestionKind::Single { options }, AnswerValue::Choice(c)) => match c.as_slice() {
+                [] if self.required => Some("必填".into()),
+                [] => None,
+                [v] if known(options, v) => None,
+                [_] => Some("选项无效".into()),
+                _ => Some("只能选择一项".into()),
+            },
+            (QuestionKind::Multiple { options }, AnswerValue::Choice(c)) => {
+                if c.is_empty() && self.required {
+                    Some("必填".into())
+                } else if c.iter().any(|v| !known(options, v)) {
+                    Some("选项无效".into())
+                } else {
+                    None
+                }
+            }
+            (QuestionKind::Text { max_len }, AnswerValue::Text(t)) => {
+                let len = t.trim().chars().count();
+                if len == 0 && self.required {
+                    Some("必填".into())
+                } else if len > *max_len {
+                    Some(format!("不能超过 {} 字", max_len))
+                } else {
+                    None
+                }
+            }
+            _ => Some("答案类型与题型不符".into()),
+        }
+    }
+}
+
+impl EvaluationForm {
+    pub async fn fetch(service: &YouthService, item_id: &str) -> Result<Self> {
+        let data = service
+            .get_result(FORM_URL, Some(json!({ "itemId": item_id })))
+            .await?;
+        let list = data
+            .as_array()
+            .or_else(|| data.get("questions").and_then(|q| q.as_array()))
+            .context("Evaluation form missing questions")?;
+        Ok(Self {
+            item_id: item_id.to_string(),
+            questions: list.iter().map(Question::from_raw).collect::<Result<_>>()?,
+        })
+    }
+
+    /// 逐题校验；未知题目 ID 也视为错误。
+    pub fn validate(&self, answers: &[Answer]) -> Vec<AnswerError> {
+        let mut errors: Vec<AnswerError> = answers
+            .iter()
+            .filter(|a| !self.questions.iter().any(|q| q.id == a.question_id))
+            .map(|a| AnswerError {
+                question_id: a.question_id.clone(),
+                message: "题目不存在".into(),
+            })
+            .collect();
+        for q in &self.questions {
+            let answer = answers
+                .iter()
+                .find(|a| a.question_id == q.id)
+                .map(|a| &a.value);
+            if let Some(message) = q.check(answer) {
+                errors.push(AnswerError {
+                    question_id: q.id.clone(),
+                    message,
+                });
+            }
+        }
+        errors
+    }
+
+    pub async fn submit(&self, service: &YouthService, answers: &[Answer]) -> Result<()> {
+        if let Some(e) = self.validate(answers).first() {
+            bail!("Invalid answer for {}: {}", e.question_id, e.message);
+        }
+        let answers: Vec<Value> = answers
+            .iter()
+            .map(|a| {
+                let value = match &a.value {
+                    AnswerValue::Rating(r) => json!(r.to_string()),
+                    AnswerValue::Choice(c) => json!(c.join(",")),
+                    AnswerValue::Text(t) => json!(t.trim()),
+                };
+                json!({ "questionId": a.question_id, "answer": value })
+            })
+            .collect();
+        let body = json!({ "itemId": self.item_id, "answers": answers });
+        service
+            .request(SUBMIT_URL, "post", None, Some(body))
+            .await?;
+        Ok(())
+    }
+}
+
+/// 活动已结束、开启了评价且尚未评价。
+pub fn awaiting_evaluation(sc: &SecondClass, now: CstDateTime) -> bool {
+    let enabled = raw_flag(sc.raw.get("evaluation"));
+    let evaluated = raw_flag(sc.raw.get("evaluated"));
+    let ended = sc.hold_time().map(|t| t.end < now).unwrap_or(false);
+    enabled && !evaluated && ended
+}
+
+pub fn pending(participated: &[SecondClass], now: CstDateTime) -> Vec<SecondClass> {
+    participated
+        .iter()
+        .filter(|sc| awaiting_evaluation(sc, now))
+        .cloned()
+        .collect()
+}
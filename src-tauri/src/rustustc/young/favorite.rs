@@ -0,0 +1,111 @@
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::SecondClass;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// ==================== 收藏 (Favorite) ====================
+//
+// 服务器收藏接口不可用时，收藏/取消收藏先记在本地，之后由 `sync` 按顺序补发。
+// 本地状态优先于活动数据中的 `enshrine` 字段。
+//
+// 未经验证：`mobile/item/enshrine/{id}`、`mobile/item/cancelEnshrine/{id}` 与
+// `item/scItemEnshrine/list` 三个接口路径是按其他二课接口的命名推测的，尚未用真实账号抓包确认。
+// 因此除网络问题与会话失效外，收藏接口返回的 404、`YOUTH_API_ERROR` 或无法解析的响应也按
+// 「接口暂不可用」进入队列；只有名额、时间冲突等规则类错误直接返回，补发时遇到则丢弃该操作。
+
+/// 网络问题或会话失效，稍后原样重发可能成功。
+pub fn transient(err: &AppError) -> bool {
+    err.retryable() || matches!(err, AppError::SessionExpired(_) | AppError::AuthRequired)
+}
+
+/// 收藏接口调用失败后是否留待补发：临时故障，或接口本身不可用。
+pub fn should_queue(err: &AppError) -> bool {
+    transient(err) || matches!(err, AppError::Youth(_) | AppError::Parse(_))
+}
+
+/// 尚未同步到服务器的一次操作。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingOp {
+    pub id: String,
+    pub favorite: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FavoriteCache {
+    /// 收藏的活动快照（服务器列表 + 本地未同步的收藏）
+    pub items: Vec<SecondClass>,
+    /// 未同步的操作；同一活动只保留最后一次
+    pub pending: Vec<PendingOp>,
+}
+
+impl FavoriteCache {
+    fn set_item(&mut self, sc: &SecondClass, favorite: bool) {
+        self.items.retain(|i| i.id != sc.id);
+        if favorite {
+            self.items.push(sc.clone());
+        }
+    }
+
+    /// 服务器已确认的操作：更新快照，并丢弃该活动的未同步操作。
+    pub fn confirm(&mut self, sc: &SecondClass, favorite: bool) {
+        self.set_item(sc, favorite);
+        self.pending.retain(|op| op.id != sc.id);
+    }
+
+    /// 服务器不可用时记录本地操作。
+    pub fn record(&mut self, sc: &SecondClass, favorite: bool) {
+        self.set_item(sc, favorite);
+        self.queue(&sc.id, favorite);
+    }
+
+    /// 拿不到活动详情时只记录操作：取消收藏时移除快照，收藏时保留已有快照，
+    /// 不缓存没有名称的占位数据，等同步后由服务器列表补全。
+    pub fn record_id(&mut self, id: &str, favorite: bool) {
+        if !favorite {
+            self.items.retain(|i| i.id != id);
+        }
+        self.queue(id, favorite);
+    }
+
+    fn queue(&mut self, id: &str, favorite: bool) {
+        self.pending.retain(|op| op.id != id);
+        self.pending.push(PendingOp {
+            id: id.to_string(),
+            favorite,
+        });
+    }
+
+    /// 用服务器列表替换快照，再叠加未同步的操作。
+    pub fn replace_remote(&mut self, remote: Vec<SecondClass>) {
+        let local = std::mem::replace(&mut self.items, remote);
+        for op in self.pending.clone() {
+            if op.favorite {
+                if let Some(sc) = local.iter().find(|i| i.id == op.id) {
+                    self.set_item(&sc.clone(), true);
+                }
+            } else {
+                self.items.retain(|i| i.id != op.id);
+            }
+        }
+    }
+
+    pub fn is_favorite(&self, sc: &SecondClass) -> bool {
+        if let Some(op) = self.pending.iter().find(|op| op.id == sc.id) {
+            return op.favorite;
+        }
+        sc.is_favorite() || self.items.iter().any(|i| i.id == sc.id)
+    }
+
+    /// 序列化活动并附加 `is_favorite` 字段，供列表接口使用。
+    pub fn annotate(&self, sc: &SecondClass) -> Value {
+        let mut v = serde_json::to_value(sc).unwrap_or(Value::Null);
+        if let Value::Object(map) = &mut v {
+            map.insert("is_favorite".to_string(), Value::Bool(self.is_favorite(sc)));
+        }
+        v
+    }
+
+    pub fn annotate_all<'a>(&self, list: impl IntoIterator<Item = &'a SecondClass>) -> Value {
+        Value::Array(list.into_iter().map(|sc| self.annotate(sc)).collect())
+    }
+}
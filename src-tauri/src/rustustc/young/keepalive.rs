@@ -0,0 +1,110 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// ==================== 会话保活 (KeepAlive) ====================
+//
+// 后台定时检查 CAS 登录态（`is_login`）与二课 token（`scMyInfo/info`）。
+// token 失效或使用超过 `refresh_after_minutes` 时用 CAS Cookie 刷新；CAS 失效时才用存储的凭据重新登录。
+// 离线时检查间隔按 2 的幂次延长，最长 `max_backoff_minutes`。
+
+/// 检查间隔上限
+pub const MAX_INTERVAL_MINUTES: u32 = 120;
+/// 主动刷新与退避的上限（1 天）
+pub const MAX_REFRESH_MINUTES: u32 = 24 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeepAliveSettings {
+    pub enabled: bool,
+    /// 两次检查的间隔
+    pub interval_minutes: u32,
+    /// token 使用超过该时长后主动刷新
+    pub refresh_after_minutes: u32,
+    /// 离线时最长的检查间隔
+    pub max_backoff_minutes: u32,
+}
+
+impl Default for KeepAliveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 10,
+            refresh_after_minutes: 90,
+            max_backoff_minutes: 60,
+        }
+    }
+}
+
+impl KeepAliveSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.interval_minutes == 0 || self.interval_minutes > MAX_INTERVAL_MINUTES {
+            bail!(
+                "Keep-alive interval must be between 1 and {} minutes",
+                MAX_INTERVAL_MINUTES
+            );
+        }
+        if self.refresh_after_minutes < self.interval_minutes
+            || self.refresh_after_minutes > MAX_REFRESH_MINUTES
+        {
+            bail!("Refresh time must be between the check interval and 1 day");
+        }
+        if self.max_backoff_minutes < self.interval_minutes
+            || self.max_backoff_minutes > MAX_REFRESH_MINUTES
+        {
+            bail!("Maximum backoff must be between the check interval and 1 day");
+        }
+        Ok(())
+    }
+
+    /// 连续失败 `failures` 次后距下次检查的时长。
+    pub fn next_delay(&self, failures: u32) -> Duration {
+        let minutes = (self.interval_minutes as u64)
+            .saturating_mul(1 << failures.min(16))
+            .min(self.max_backoff_minutes.max(self.interval_minutes) as u64);
+        Duration::from_secs(minutes * 60)
+    }
+}
+
+/// 一次检查的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    Valid,
+    Expired,
+    /// 网络不可用，无法判断
+    Offline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepAliveAction {
+    /// 无需处理
+    Healthy,
+    /// 用 CAS Cookie 重新获取 token
+    Refresh,
+    /// CAS 已失效，需要用存储的凭据重新登录
+    Relogin,
+    /// 离线，稍后再试
+    Backoff,
+}
+
+/// 按 CAS 与 token 的检查结果决定下一步。`token_age` 为当前 token 的使用时长。
+pub fn decide(
+    cas: Health,
+    youth: Health,
+    token_age: Duration,
+    settings: &KeepAliveSettings,
+) -> KeepAliveAction {
+    if cas == Health::Offline || youth == Health::Offline {
+        return KeepAliveAction::Backoff;
+    }
+    if cas == Health::Expired {
+        return KeepAliveAction::Relogin;
+    }
+    let refresh_after = Duration::from_secs(settings.refresh_after_minutes as u64 * 60);
+    if youth == Health::Expired || token_age >= refresh_after {
+        KeepAliveAction::Refresh
+    } else {
+        KeepAliveAction::Healthy
+    }
+}
@@ -1,5 +1,34 @@
+pub mod appeal;
+pub mod batch;
+pub mod checkin;
+pub mod conflict;
+pub mod evaluation;
+pub mod favorite;
+pub mod keepalive;
 pub mod model;
+pub mod place;
+pub mod query;
+pub mod rate_limit;
+pub mod reminder;
+pub mod saved_search;
+pub mod series;
 pub mod service;
+pub mod session;
+pub mod sniper;
+pub mod tag_index;
+pub mod team;
+pub mod waitlist;
+pub mod watch;
+pub mod work;
 
+pub use appeal::{Appeal, AppealStatus};
+pub use checkin::{CheckKind, CheckinPayload};
+pub use conflict::{ConflictPlan, ConflictPolicy};
+pub use evaluation::{Answer, EvaluationForm};
 pub use model::{SCFilter, SecondClass, Status};
+pub use place::{Campus, CampusDictionary, Place};
+pub use query::{Query, QueryError};
+pub use series::Series;
 pub use service::YouthService;
+pub use tag_index::{TagIndex, TagKind};
+pub use team::{Team, TeamLimits};
@@ -1,27 +1,115 @@
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::conflict::{self, ConflictPolicy};
+use crate::rustustc::young::place::{Campus, CampusDictionary};
 use crate::rustustc::young::service::YouthService;
 use anyhow::{bail, Context, Result};
-use chrono::NaiveDateTime;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 // ==================== 基础类型定义 (TimePeriod) ====================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 二课网所有时间均为北京时间，统一按 Asia/Shanghai 解释，与设备时区无关。
+pub const SHANGHAI: Tz = chrono_tz::Asia::Shanghai;
+
+pub type CstDateTime = DateTime<Tz>;
+
+/// 当前北京时间。
+pub fn now() -> CstDateTime {
+    Utc::now().with_timezone(&SHANGHAI)
+}
+
+/// 将北京时间的本地时间转换为带时区的时间（上海无夏令时，转换总是唯一的）。
+pub fn cst(naive: NaiveDateTime) -> CstDateTime {
+    SHANGHAI
+        .from_local_datetime(&naive)
+        .earliest()
+        .unwrap_or_else(|| SHANGHAI.from_utc_datetime(&naive))
+}
+
+/// 接口中的布尔标志可能是 `1` / `"1"` / `true`。
+pub(crate) fn raw_flag(v: Option<&Value>) -> bool {
+    match v {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_i64() == Some(1),
+        Some(Value::String(s)) => s == "1" || s == "true",
+        _ => false,
+    }
+}
+
+/// 序列化为带偏移的 RFC 3339；反序列化同时接受带偏移的时间与无时区的北京时间。
+pub mod cst_serde {
+    use super::{cst, CstDateTime, SHANGHAI};
+    use chrono::{DateTime, NaiveDateTime};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(dt: &CstDateTime, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&dt.format("%Y-%m-%dT%H:%M:%S%:z").to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<CstDateTime, D::Error> {
+        let s = String::deserialize(d)?;
+        if let Ok(dt) = DateTime::parse_from_rfc3339(&s) {
+            return Ok(dt.with_timezone(&SHANGHAI));
+        }
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S"]
+            .iter()
+            .find_map(|f| NaiveDateTime::parse_from_str(&s, f).ok())
+            .map(cst)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid datetime: {}", s)))
+    }
+
+    /// `Option<CstDateTime>` 版本，配合 `#[serde(default)]` 使用。
+    pub mod option {
+        use super::CstDateTime;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(dt: &Option<CstDateTime>, s: S) -> Result<S::Ok, S::Error> {
+            match dt {
+                Some(dt) => super::serialize(dt, s),
+                None => s.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            d: D,
+        ) -> Result<Option<CstDateTime>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] CstDateTime);
+            Ok(Option::<Wrapper>::deserialize(d)?.map(|Wrapper(dt)| dt))
+        }
+    }
+}
+
+/// 闭区间 `[start, end]`，与接口中活动时间的含义一致：首尾相接也视为重叠。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TimePeriod {
-    pub start: NaiveDateTime,
-    pub end: NaiveDateTime,
+    #[serde(with = "cst_serde")]
+    pub start: CstDateTime,
+    #[serde(with = "cst_serde")]
+    pub end: CstDateTime,
 }
 
 impl TimePeriod {
-    pub fn new(start: NaiveDateTime, end: NaiveDateTime) -> Result<Self> {
+    pub fn new(start: CstDateTime, end: CstDateTime) -> Result<Self> {
         if start > end {
             bail!("The start time should be earlier than the end time");
         }
         Ok(Self { start, end })
     }
 
-    pub fn parse(s: &str) -> Result<NaiveDateTime> {
-        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").context("Failed to parse datetime")
+    /// 以北京时间的本地时间构造。
+    pub fn from_naive(start: NaiveDateTime, end: NaiveDateTime) -> Result<Self> {
+        Self::new(cst(start), cst(end))
+    }
+
+    pub fn parse(s: &str) -> Result<CstDateTime> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .map(cst)
+            .context("Failed to parse datetime")
     }
 
     pub fn parse_period(start_str: &str, end_str: Option<&str>) -> Result<Self> {
@@ -41,6 +129,134 @@ impl TimePeriod {
     pub fn is_overlap(&self, other: &TimePeriod) -> bool {
         self.start <= other.end && self.end >= other.start
     }
+
+    pub fn contains_time(&self, t: &CstDateTime) -> bool {
+        self.start <= *t && *t <= self.end
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// 交集；与 `is_overlap` 一致，首尾相接时返回零长度区间。
+    pub fn intersect(&self, other: &TimePeriod) -> Option<TimePeriod> {
+        if !self.is_overlap(other) {
+            return None;
+        }
+        Some(TimePeriod {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// 两段重叠或相接时合并为一段，否则返回 `None`。
+    pub fn union(&self, other: &TimePeriod) -> Option<TimePeriod> {
+        if !self.is_overlap(other) {
+            return None;
+        }
+        Some(TimePeriod {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// 去掉与 `other` 重叠的部分，剩余 0~2 段（不含零长度片段）。
+    pub fn subtract(&self, other: &TimePeriod) -> Vec<TimePeriod> {
+        if !self.is_overlap(other) {
+            return vec![*self];
+        }
+        let mut rest = Vec::new();
+        if self.start < other.start {
+            rest.push(TimePeriod {
+                start: self.start,
+                end: other.start,
+            });
+        }
+        if other.end < self.end {
+            rest.push(TimePeriod {
+                start: other.end,
+                end: self.end,
+            });
+        }
+        rest
+    }
+
+    /// 排序并合并所有重叠或相接的区间。
+    pub fn merge_all(mut periods: Vec<TimePeriod>) -> Vec<TimePeriod> {
+        periods.sort_by_key(|p| (p.start, p.end));
+        let mut merged: Vec<TimePeriod> = Vec::with_capacity(periods.len());
+        for p in periods {
+            match merged.last_mut() {
+                Some(last) if last.is_overlap(&p) => last.end = last.end.max(p.end),
+                _ => merged.push(p),
+            }
+        }
+        merged
+    }
+}
+
+/// 一组互不相交、按时间排序的占用时段，用于冲突检测与空闲时间计算。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeSlotSet {
+    slots: Vec<TimePeriod>,
+}
+
+impl TimeSlotSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_periods(periods: Vec<TimePeriod>) -> Self {
+        Self {
+            slots: TimePeriod::merge_all(periods),
+        }
+    }
+
+    pub fn insert(&mut self, period: TimePeriod) {
+        let mut all = std::mem::take(&mut self.slots);
+        all.push(period);
+        self.slots = TimePeriod::merge_all(all);
+    }
+
+    pub fn remove(&mut self, period: &TimePeriod) {
+        self.slots = self.slots.iter().flat_map(|s| s.subtract(period)).collect();
+    }
+
+    pub fn slots(&self) -> &[TimePeriod] {
+        &self.slots
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.slots
+            .iter()
+            .fold(Duration::zero(), |acc, s| acc + s.duration())
+    }
+
+    pub fn overlaps(&self, period: &TimePeriod) -> bool {
+        self.slots.iter().any(|s| s.is_overlap(period))
+    }
+
+    /// 与 `period` 重叠的部分。
+    pub fn conflicts(&self, period: &TimePeriod) -> Vec<TimePeriod> {
+        self.slots
+            .iter()
+            .filter_map(|s| s.intersect(period))
+            .collect()
+    }
+
+    /// `window` 内未被占用、且不短于 `min_len` 的空闲时段。
+    pub fn free_gaps(&self, window: &TimePeriod, min_len: Duration) -> Vec<TimePeriod> {
+        let mut free = vec![*window];
+        for slot in &self.slots {
+            free = free.iter().flat_map(|f| f.subtract(slot)).collect();
+        }
+        free.retain(|f| f.duration() >= min_len && f.duration() > Duration::zero());
+        free
+    }
 }
 
 // ==================== Tag 系统 (Module, Department, Label) ====================
@@ -168,6 +384,45 @@ impl Tag for Label {
 
 // ==================== 过滤器 SCFilter ====================
 
+/// 学时比较运算符，用于 `hours>=2` 一类的条件。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CmpOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+impl CmpOp {
+    pub fn eval(&self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CmpOp::Gt => lhs > rhs,
+            CmpOp::Ge => lhs >= rhs,
+            CmpOp::Lt => lhs < rhs,
+            CmpOp::Le => lhs <= rhs,
+            CmpOp::Eq => (lhs - rhs).abs() < 1e-6,
+        }
+    }
+
+    /// 取反后的运算符；`Eq` 没有单一的反面，返回 `None`。
+    pub fn negate(&self) -> Option<Self> {
+        match self {
+            CmpOp::Gt => Some(CmpOp::Le),
+            CmpOp::Ge => Some(CmpOp::Lt),
+            CmpOp::Lt => Some(CmpOp::Ge),
+            CmpOp::Le => Some(CmpOp::Gt),
+            CmpOp::Eq => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HourBound {
+    pub op: CmpOp,
+    pub value: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SCFilter {
     pub name: String,
@@ -177,6 +432,32 @@ pub struct SCFilter {
     pub labels: Vec<Label>,
     pub fuzzy_name: bool,
     pub strict_time: bool,
+
+    // 以下条件接口不支持，仅在本地 check 中生效
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub exclude_keywords: Vec<String>,
+    #[serde(default)]
+    pub exclude_modules: Vec<Module>,
+    #[serde(default)]
+    pub exclude_departments: Vec<Department>,
+    #[serde(default)]
+    pub exclude_labels: Vec<Label>,
+    #[serde(default)]
+    pub statuses: Vec<Status>,
+    #[serde(default)]
+    pub exclude_statuses: Vec<Status>,
+    #[serde(default)]
+    pub hours: Vec<HourBound>,
+    /// 任一地点位于所列校区；无法识别校区的活动不过滤
+    #[serde(default)]
+    pub campuses: Vec<Campus>,
+    #[serde(default)]
+    pub exclude_campuses: Vec<Campus>,
+    /// 校区识别字典，缺省使用内置字典
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campus_dictionary: Option<CampusDictionary>,
 }
 
 impl SCFilter {
@@ -210,6 +491,45 @@ impl SCFilter {
         self.labels.push(label);
         self
     }
+    pub fn add_status(mut self, status: Status) -> Self {
+        self.statuses.push(status);
+        self
+    }
+    pub fn add_hour_bound(mut self, op: CmpOp, value: f64) -> Self {
+        self.hours.push(HourBound { op, value });
+        self
+    }
+
+    /// 需要查询的列表：(报名中列表, 已结束列表)。
+    ///
+    /// 未限定状态时只查报名中；否则按 `statuses` 去掉 `exclude_statuses` 后的状态集合决定，
+    /// 集合同时含「报名中」与其他状态时两个列表都查。
+    pub fn status_lists(&self) -> (bool, bool) {
+        if self.statuses.is_empty() && self.exclude_statuses.is_empty() {
+            return (true, false);
+        }
+        let wanted: Vec<Status> = if self.statuses.is_empty() {
+            Status::ALL.to_vec()
+        } else {
+            self.statuses.clone()
+        };
+        let wanted: Vec<Status> = wanted
+            .into_iter()
+            .filter(|s| !self.exclude_statuses.contains(s))
+            .collect();
+        (
+            wanted.contains(&Status::Applying),
+            wanted.iter().any(|s| *s != Status::Applying),
+        )
+    }
+    pub fn add_campus(mut self, campus: Campus) -> Self {
+        self.campuses.push(campus);
+        self
+    }
+    pub fn campus_dictionary(mut self, dict: CampusDictionary) -> Self {
+        self.campus_dictionary = Some(dict);
+        self
+    }
 
     pub fn to_params(&self) -> Value {
         let mut params = json!({});
@@ -266,6 +586,9 @@ impl SCFilter {
         if !self.fuzzy_name && sc.name != self.name {
             return false;
         }
+        if !self.check_local(sc) {
+            return false;
+        }
         if let Some(period) = &self.time_period {
             if let Ok(ht) = sc.hold_time() {
                 if self.strict_time {
@@ -281,6 +604,84 @@ impl SCFilter {
         }
         true
     }
+
+    /// 接口无法过滤的条件：关键词、排除项、状态、学时与校区。
+    fn check_local(&self, sc: &SecondClass) -> bool {
+        let name = sc.name.to_lowercase();
+        if !self
+            .keywords
+            .iter()
+            .all(|k| name.contains(&k.to_lowercase()))
+        {
+            return false;
+        }
+        if self
+            .exclude_keywords
+            .iter()
+            .any(|k| name.contains(&k.to_lowercase()))
+        {
+            return false;
+        }
+
+        if let Some(sc_m) = sc.module() {
+            if self.exclude_modules.iter().any(|m| m.value == sc_m.value) {
+                return false;
+            }
+        }
+        if let Some(sc_d) = sc.department() {
+            if self.exclude_departments.iter().any(|d| d.id == sc_d.id) {
+                return false;
+            }
+        }
+        if !self.exclude_labels.is_empty() {
+            let sc_labels = sc.labels();
+            if self
+                .exclude_labels
+                .iter()
+                .any(|t| sc_labels.iter().any(|s| s.id == t.id))
+            {
+                return false;
+            }
+        }
+
+        let status = sc.status();
+        if !self.statuses.is_empty() && !self.statuses.contains(&status) {
+            return false;
+        }
+        if self.exclude_statuses.contains(&status) {
+            return false;
+        }
+
+        if !self.hours.is_empty() {
+            match sc.hours() {
+                Some(h) => {
+                    if !self.hours.iter().all(|b| b.op.eval(h, b.value)) {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        if !self.campuses.is_empty() || !self.exclude_campuses.is_empty() {
+            let dict = self
+                .campus_dictionary
+                .as_ref()
+                .unwrap_or_else(|| CampusDictionary::default_ref());
+            let campuses = dict.campuses(sc);
+            if !campuses.is_empty() {
+                if !self.campuses.is_empty() && !campuses.iter().any(|c| self.campuses.contains(c))
+                {
+                    return false;
+                }
+                // 多地点活动只要还有未排除的校区即可参加
+                if campuses.iter().all(|c| self.exclude_campuses.contains(c)) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
 }
 
 // ==================== User & SignInfo ====================
@@ -353,7 +754,7 @@ impl User {
         let raw = service.page_search(url, params, max, size).await?;
         Ok(raw
             .into_iter()
-            .map(|v| serde_json::from_value(v))
+            .map(serde_json::from_value)
             .collect::<Result<Vec<_>, _>>()?)
     }
 }
@@ -367,7 +768,71 @@ pub struct SignInfo {
     pub remarks: String,
 }
 
+/// 报名信息中某个字段的校验错误。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^1[3-9]\d{9}$").unwrap());
+static EMAIL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(\.[A-Za-z0-9-]+)+$").unwrap());
+
 impl SignInfo {
+    /// 备注的最大长度（字符数）
+    pub const MAX_REMARKS: usize = 200;
+
+    /// 去掉首尾空白，手机号去掉空格、短横线与 `+86` 前缀。
+    pub fn normalized(&self) -> Self {
+        let mut phone: String = self
+            .phone
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect();
+        if let Some(rest) = phone.strip_prefix("+86") {
+            phone = rest.to_string();
+        }
+        Self {
+            college: self.college.trim().to_string(),
+            classes: self.classes.trim().to_string(),
+            phone,
+            email: self.email.trim().to_string(),
+            remarks: self.remarks.trim().to_string(),
+        }
+    }
+
+    /// 校验各字段，返回全部错误（为空表示通过）。邮箱可留空，填写时需格式正确。
+    pub fn validate(&self) -> Vec<FieldError> {
+        let info = self.normalized();
+        let mut errors = Vec::new();
+        let mut fail = |field, message: &str| {
+            errors.push(FieldError {
+                field,
+                message: message.to_string(),
+            })
+        };
+
+        if info.college.is_empty() {
+            fail("college", "学院不能为空");
+        }
+        if info.classes.is_empty() {
+            fail("classes", "班级不能为空");
+        } else if info.classes.chars().count() > 32 {
+            fail("classes", "班级过长");
+        }
+        if !PHONE_RE.is_match(&info.phone) {
+            fail("phone", "手机号应为 11 位大陆手机号");
+        }
+        if !info.email.is_empty() && !EMAIL_RE.is_match(&info.email) {
+            fail("email", "邮箱格式不正确");
+        }
+        if info.remarks.chars().count() > Self::MAX_REMARKS {
+            fail("remarks", "备注过长");
+        }
+        errors
+    }
+
     pub async fn get_self(service: &YouthService) -> Result<Self> {
         let mut user = User::get_current(service).await?;
         let phone = user.get_phone(service).await?.unwrap_or_default();
@@ -383,7 +848,7 @@ impl SignInfo {
 
 // ==================== Status ====================
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Serialize, Deserialize)]
 pub enum Status {
     Applying = 26,
     ApplyEnded = 28,
@@ -399,6 +864,19 @@ pub enum Status {
 }
 
 impl Status {
+    pub const ALL: [Status; 10] = [
+        Status::Applying,
+        Status::ApplyEnded,
+        Status::HourPublic,
+        Status::HourAppendPublic,
+        Status::PublicEnded,
+        Status::HourApplying,
+        Status::HourApproved,
+        Status::HourRejected,
+        Status::Finished,
+        Status::AbnormalFinished,
+    ];
+
     pub fn code(&self) -> i32 {
         *self as i32
     }
@@ -649,11 +1127,42 @@ pub struct SecondClass {
 */
 
 impl SecondClass {
+    /// 只有 ID 的占位对象，通常随后调用 `update` 获取详情。
+    pub fn from_id(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: String::new(),
+            status_code: 0,
+            valid_hour: None,
+            apply_num: None,
+            apply_limit: None,
+            boolean_registration: None,
+            need_sign_info_str: None,
+            conceive: None,
+            base_content: None,
+            item_category: None,
+            create_time_str: None,
+            apply_start: None,
+            apply_end: None,
+            start_time: None,
+            end_time: None,
+            tel: None,
+            raw: Value::Null,
+        }
+    }
+
+    /// 按 ID 从 `queryById` 获取活动详情。
+    pub async fn get_by_id(service: &YouthService, id: &str) -> Result<Self> {
+        let mut sc = Self::from_id(id);
+        sc.update(service).await?;
+        Ok(sc)
+    }
+
     pub fn status(&self) -> Status {
         Status::from(self.status_code)
     }
 
-    pub fn create_time(&self) -> Result<NaiveDateTime> {
+    pub fn create_time(&self) -> Result<CstDateTime> {
         TimePeriod::parse(self.create_time_str.as_deref().unwrap_or(""))
     }
 
@@ -689,6 +1198,16 @@ impl SecondClass {
         self.item_category.as_deref() == Some("1")
     }
 
+    /// 活动学时：优先 `validHour`，缺失时回退到 `raw.serviceHour`（字符串）。
+    pub fn hours(&self) -> Option<f64> {
+        self.valid_hour.or_else(|| {
+            self.raw
+                .get("serviceHour")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.trim().parse().ok())
+        })
+    }
+
     pub fn module(&self) -> Option<Module> {
         Some(Module {
             value: self.raw.get("module")?.as_str()?.to_string(),
@@ -736,6 +1255,50 @@ impl SecondClass {
         result
     }
 
+    /// 学时被驳回（`HourRejected`）时详情中的驳回原因。
+    pub fn hour_reject_reason(&self) -> Option<String> {
+        if self.status() != Status::HourRejected {
+            return None;
+        }
+        ["examineOpinion", "hourRejectReason", "auditOpinion"]
+            .iter()
+            .find_map(|&key| self.raw.get(key).and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    /// 举办地点（`placeInfo`），多地点时取 `itemPlaceDTO.places` 拼接。
+    pub fn place(&self) -> Option<String> {
+        let non_empty = |v: &Value| {
+            v.as_str()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        };
+        if let Some(place) = self.raw.get("placeInfo").and_then(non_empty) {
+            return Some(place);
+        }
+        let places: Vec<String> = self
+            .raw
+            .pointer("/itemPlaceDTO/places")?
+            .as_array()?
+            .iter()
+            .filter_map(|p| p.get("placeInfo").and_then(non_empty))
+            .collect();
+        (!places.is_empty()).then(|| places.join("、"))
+    }
+
+    /// 主办方名称，缺失时回退到联系人。
+    pub fn organizer(&self) -> Option<String> {
+        ["organizer_dictText", "linkMan"]
+            .iter()
+            .find_map(|&key| self.raw.get(key).and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
     // === 对应 Python @cached_property children ===
     pub async fn get_children(&self, service: &YouthService) -> Result<Vec<SecondClass>> {
         if !self.is_series() {
@@ -840,36 +1403,59 @@ impl SecondClass {
         Ok(result)
     }
 
+    /// 按 `filter.status_lists()` 查询报名中列表和/或已结束列表，合并并按 ID 去重。
+    pub async fn find_by_status(
+        service: &YouthService,
+        filter: SCFilter,
+    ) -> Result<Vec<SecondClass>> {
+        let (applying, ended) = filter.status_lists();
+        let mut activities = Vec::new();
+        if applying {
+            activities = Self::find(service, filter.clone(), false, false, -1).await?;
+        }
+        if ended {
+            for sc in Self::find(service, filter, true, false, -1).await? {
+                if !activities.iter().any(|a| a.id == sc.id) {
+                    activities.push(sc);
+                }
+            }
+        }
+        Ok(activities)
+    }
+
     pub async fn get_participated(service: &YouthService) -> Result<Vec<SecondClass>> {
         let url = "item/scParticipateItem/list";
         let raw = service.page_search(url, json!({}), -1, 20).await?;
         let list = raw
             .into_iter()
-            .map(|v| serde_json::from_value(v))
+            .map(serde_json::from_value)
             .collect::<Result<Vec<_>, _>>()?;
         Ok(list)
     }
 
+    /// 报名该活动。`auto_cancel` 时遇到时间冲突会取消冲突活动后重试，
+    /// `record` 返回被取消活动原先的报名信息，回滚时用于恢复报名（返回 `None` 时使用默认信息）。
     pub async fn apply(
         &self,
         service: &YouthService,
         force: bool,
         auto_cancel: bool,
         sign_info: Option<SignInfo>,
+        record: impl Fn(&str) -> Option<SignInfo> + Send,
     ) -> Result<bool> {
         if !force && !self.applyable() {
             return Ok(false);
         }
 
         let url = format!("mobile/item/enter/{}", self.id);
-        let json_body = if self.need_sign_info() {
+        let (json_body, sign_info) = if self.need_sign_info() {
             let info = match sign_info {
                 Some(s) => s,
                 None => SignInfo::get_self(service).await?,
             };
-            json!(info)
+            (json!(info), Some(info))
         } else {
-            json!({})
+            (json!({}), None)
         };
 
         let res = service.request(&url, "post", None, Some(json_body)).await;
@@ -878,16 +1464,27 @@ impl SecondClass {
             Err(e) => {
                 let msg = e.to_string();
                 if auto_cancel && msg.contains("时间冲突") {
-                    let my_classes = Self::get_participated(service).await?;
-                    let my_hold_time = self.hold_time()?;
-                    for c in my_classes {
-                        if let Ok(ht) = c.hold_time() {
-                            if ht.is_overlap(&my_hold_time) {
-                                c.cancel_apply(service).await?;
-                            }
-                        }
+                    // 只取消尚未开始的冲突活动，报名仍失败时恢复已取消的报名
+                    let (plan, outcome) = Box::pin(conflict::plan_and_resolve(
+                        service,
+                        self,
+                        &ConflictPolicy::default(),
+                        force,
+                        sign_info,
+                        false,
+                        record,
+                    ))
+                    .await?;
+                    match outcome {
+                        Some(o) if o.applied => Ok(true),
+                        Some(o) => Err(AppError::TimeConflict(o.summary()).into()),
+                        None => Err(AppError::TimeConflict(format!(
+                            "{}: {}",
+                            msg,
+                            plan.reason.unwrap_or_default()
+                        ))
+                        .into()),
                     }
-                    Box::pin(self.apply(service, force, false, None)).await
                 } else {
                     Err(e)
                 }
@@ -903,6 +1500,38 @@ impl SecondClass {
         Ok(true)
     }
 
+    /// 是否已收藏（`enshrine` 为 1）。
+    pub fn is_favorite(&self) -> bool {
+        raw_flag(self.raw.get("enshrine"))
+    }
+
+    pub fn favorite_count(&self) -> Option<i64> {
+        self.raw.get("enshrineNum").and_then(|v| v.as_i64())
+    }
+
+    /// 未经验证：收藏相关的三个接口路径为推测，见 `favorite` 模块说明。
+    pub async fn favorite(&self, service: &YouthService) -> Result<()> {
+        let url = format!("mobile/item/enshrine/{}", self.id);
+        service.request(&url, "post", None, None).await?;
+        Ok(())
+    }
+
+    pub async fn unfavorite(&self, service: &YouthService) -> Result<()> {
+        let url = format!("mobile/item/cancelEnshrine/{}", self.id);
+        service.request(&url, "post", None, None).await?;
+        Ok(())
+    }
+
+    pub async fn list_favorites(service: &YouthService) -> Result<Vec<SecondClass>> {
+        let url = "item/scItemEnshrine/list";
+        let raw = service.page_search(url, json!({}), -1, 20).await?;
+        let list = raw
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(list)
+    }
+
     pub async fn update(&mut self, service: &YouthService) -> Result<()> {
         let url = "item/scItem/queryById";
         let params = json!({ "id": self.id });
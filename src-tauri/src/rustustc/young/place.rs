@@ -0,0 +1,226 @@
+use crate::rustustc::young::model::{CstDateTime, SecondClass, TimePeriod, SHANGHAI};
+use chrono::{Datelike, TimeZone, Timelike, Weekday};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// ==================== 地点与校区 (Place) ====================
+//
+// `itemPlaceDTO.places` 可列出多个地点，每个地点可有自己的场次时间 `placeSt` / `placeEt`；
+// 缺失时回退到 `placeInfo` 文本。校区由可配置的关键词字典从地点名称中识别。
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Campus {
+    East,
+    West,
+    Central,
+    South,
+    North,
+    HighTech,
+}
+
+impl Campus {
+    pub const ALL: [Campus; 6] = [
+        Campus::East,
+        Campus::West,
+        Campus::Central,
+        Campus::South,
+        Campus::North,
+        Campus::HighTech,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Campus::East => "east",
+            Campus::West => "west",
+            Campus::Central => "central",
+            Campus::South => "south",
+            Campus::North => "north",
+            Campus::HighTech => "high_tech",
+        }
+    }
+
+    pub fn text(&self) -> &'static str {
+        match self {
+            Campus::East => "东区",
+            Campus::West => "西区",
+            Campus::Central => "中区",
+            Campus::South => "南区",
+            Campus::North => "北区",
+            Campus::HighTech => "高新区",
+        }
+    }
+
+    /// 接受中文名（可省略「区」）或英文关键字。
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let lower = s.to_ascii_lowercase();
+        Campus::ALL.into_iter().find(|c| {
+            let text = c.text();
+            s == text || s == text.trim_end_matches('区') || lower == c.key()
+        })
+    }
+}
+
+/// 一个地点及其场次时间（接口未给出时为空）。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Place {
+    pub name: String,
+    pub start: Option<CstDateTime>,
+    pub end: Option<CstDateTime>,
+    pub campus: Option<Campus>,
+}
+
+/// 校区关键词字典。地点名称包含任一关键词即归入该校区，规则按顺序匹配。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampusDictionary {
+    pub rules: Vec<CampusRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampusRule {
+    pub campus: Campus,
+    pub keywords: Vec<String>,
+}
+
+static DEFAULT_DICTIONARY: Lazy<CampusDictionary> = Lazy::new(|| {
+    let rule = |campus, keywords: &[&str]| CampusRule {
+        campus,
+        keywords: keywords.iter().map(|k| k.to_string()).collect(),
+    };
+    CampusDictionary {
+        rules: vec![
+            rule(Campus::HighTech, &["高新", "先研院", "GX"]),
+            rule(
+                Campus::East,
+                &[
+                    "东区",
+                    "东活",
+                    "东图",
+                    "水上报告厅",
+                    "郭沫若广场",
+                    "大礼堂",
+                    "第一教学楼",
+                    "第二教学楼",
+                    "五教",
+                ],
+            ),
+            rule(
+                Campus::West,
+                &["西区", "西活", "西图", "三教", "三四教", "学生活动中心"],
+            ),
+            rule(Campus::Central, &["中区", "中活", "体育中心"]),
+            rule(Campus::South, &["南区"]),
+            rule(Campus::North, &["北区"]),
+        ],
+    }
+});
+
+impl Default for CampusDictionary {
+    fn default() -> Self {
+        DEFAULT_DICTIONARY.clone()
+    }
+}
+
+impl CampusDictionary {
+    pub fn default_ref() -> &'static CampusDictionary {
+        &DEFAULT_DICTIONARY
+    }
+
+    /// 明确写出校区名（如「东区」）时优先于地标关键词。
+    pub fn classify(&self, place: &str) -> Option<Campus> {
+        let place = place.trim();
+        if place.is_empty() {
+            return None;
+        }
+        Campus::ALL
+            .into_iter()
+            .filter(|c| place.contains(c.text()))
+            .min_by_key(|c| place.find(c.text()))
+            .or_else(|| {
+                self.rules
+                    .iter()
+                    .find(|r| {
+                        r.keywords
+                            .iter()
+                            .any(|k| !k.trim().is_empty() && place.contains(k.trim()))
+                    })
+                    .map(|r| r.campus)
+            })
+    }
+
+    /// 活动涉及的校区（去重，保持地点顺序）。
+    pub fn campuses(&self, sc: &SecondClass) -> Vec<Campus> {
+        let mut out = Vec::new();
+        for p in places(sc, self) {
+            if let Some(c) = p.campus {
+                if !out.contains(&c) {
+                    out.push(c);
+                }
+            }
+        }
+        out
+    }
+}
+
+fn place_time(v: Option<&Value>) -> Option<CstDateTime> {
+    match v? {
+        Value::Number(n) => SHANGHAI.timestamp_millis_opt(n.as_i64()?).single(),
+        Value::String(s) => TimePeriod::parse(s.trim()).ok(),
+        _ => None,
+    }
+}
+
+/// 解析全部地点与场次；`itemPlaceDTO.places` 为空时回退到 `placeInfo`。
+pub fn places(sc: &SecondClass, dict: &CampusDictionary) -> Vec<Place> {
+    let name_of = |v: &Value| {
+        v.get("placeInfo")
+            .and_then(|p| p.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    };
+    let mut out: Vec<Place> = sc
+        .raw
+        .pointer("/itemPlaceDTO/places")
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| {
+                    let name = name_of(p)?;
+                    Some(Place {
+                        campus: dict.classify(&name),
+                        start: place_time(p.get("placeSt")),
+                        end: place_time(p.get("placeEt")),
+                        name,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if out.is_empty() {
+        if let Some(name) = name_of(&sc.raw) {
+            out.push(Place {
+                campus: dict.classify(&name),
+                start: None,
+                end: None,
+                name,
+            });
+        }
+    }
+    out
+}
+
+/// 工作日晚上（18:00 起）开始的场次，跨校区基本无法参加。
+pub fn is_weekday_evening(t: CstDateTime) -> bool {
+    !matches!(t.weekday(), Weekday::Sat | Weekday::Sun) && t.hour() >= 18
+}
+
+/// 用户的校区设置：常驻校区与识别字典。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CampusSettings {
+    pub home_campus: Option<Campus>,
+    #[serde(default)]
+    pub dictionary: CampusDictionary,
+}
@@ -0,0 +1,538 @@
+use crate::rustustc::young::model::{CmpOp, Label, Module, SCFilter, Status, TimePeriod};
+use crate::rustustc::young::place::Campus;
+use crate::rustustc::young::tag_index::{TagIndex, TagKind, TagMatch};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use std::fmt;
+
+// ==================== 查询语言 ====================
+//
+// 一行文本编译为 SCFilter，例如：
+//   美 dept:古琴 hours>=2 after:2025-12-01 status:applying -label:线上
+//
+// - 裸词 / "带空格的短语"：名称关键词（第一个作为接口的 itemName）
+// - `module:` `dept:` `label:`：经 TagIndex 解析名称（支持模糊与拼音首字母）
+// - `status:`：状态（英文关键字或中文状态名）
+// - `campus:`：校区（`东区` / `东` / `east`，按地点名称识别）
+// - `hours` + `: = > >= < <=`：学时比较
+// - `after:` `before:` `on:`：日期（`2025-12-01` 或 `"2025-12-01 18:00"`）
+// - 前缀 `-`：取反
+//
+// 所有位置均为字符（而非字节）下标，便于前端高亮。
+
+/// 查询串中的字符区间 `[start, end)`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (at {}..{})",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Module,
+    Department,
+    Label,
+    Status,
+    Campus,
+    Hours,
+    After,
+    Before,
+    On,
+}
+
+impl Field {
+    fn from_key(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "module" | "m" => Some(Field::Module),
+            "dept" | "department" | "d" => Some(Field::Department),
+            "label" | "tag" | "l" => Some(Field::Label),
+            "status" | "s" => Some(Field::Status),
+            "campus" | "c" => Some(Field::Campus),
+            "hours" | "hour" | "h" => Some(Field::Hours),
+            "after" => Some(Field::After),
+            "before" => Some(Field::Before),
+            "on" => Some(Field::On),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TermKind {
+    /// 裸词或引号短语
+    Text(String),
+    Field {
+        field: Field,
+        op: Option<CmpOp>,
+        value: String,
+        value_span: Span,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub negated: bool,
+    pub kind: TermKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+// ==================== 词法 / 语法 ====================
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// 读取一个值：引号短语或到下一个空白为止。
+    fn read_value(&mut self) -> Result<(String, Span), QueryError> {
+        let start = self.pos;
+        if self.peek() == Some('"') {
+            self.pos += 1;
+            let mut out = String::new();
+            loop {
+                match self.peek() {
+                    Some('"') => {
+                        self.pos += 1;
+                        return Ok((
+                            out,
+                            Span {
+                                start,
+                                end: self.pos,
+                            },
+                        ));
+                    }
+                    Some(c) => {
+                        out.push(c);
+                        self.pos += 1;
+                    }
+                    None => {
+                        return Err(QueryError::new(
+                            "引号未闭合",
+                            Span {
+                                start,
+                                end: self.pos,
+                            },
+                        ))
+                    }
+                }
+            }
+        }
+        while self.peek().is_some_and(|c| !c.is_whitespace()) {
+            self.pos += 1;
+        }
+        let value: String = self.chars[start..self.pos].iter().collect();
+        Ok((
+            value,
+            Span {
+                start,
+                end: self.pos,
+            },
+        ))
+    }
+
+    /// 尝试读取 `key` + 运算符；不是字段形式时回退位置并返回 `None`。
+    fn read_field_prefix(&mut self) -> Result<Option<(Field, Option<CmpOp>)>, QueryError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Ok(None);
+        }
+        let key: String = self.chars[start..self.pos].iter().collect();
+        let key_end = self.pos;
+
+        let op = match (self.peek(), self.chars.get(self.pos + 1).copied()) {
+            (Some(':'), _) => Some((None, 1)),
+            (Some('>'), Some('=')) => Some((Some(CmpOp::Ge), 2)),
+            (Some('<'), Some('=')) => Some((Some(CmpOp::Le), 2)),
+            (Some('>'), _) => Some((Some(CmpOp::Gt), 1)),
+            (Some('<'), _) => Some((Some(CmpOp::Lt), 1)),
+            (Some('='), _) => Some((Some(CmpOp::Eq), 1)),
+            _ => None,
+        };
+        let (op, op_len) = match op {
+            Some(o) => o,
+            None => {
+                self.pos = start;
+                return Ok(None);
+            }
+        };
+
+        let key_span = Span {
+            start,
+            end: key_end,
+        };
+        let field = Field::from_key(&key)
+            .ok_or_else(|| QueryError::new(format!("未知字段 `{}`", key), key_span))?;
+        if op.is_some() && field != Field::Hours {
+            return Err(QueryError::new(
+                format!("字段 `{}` 只支持 `:`", key),
+                Span {
+                    start: key_end,
+                    end: key_end + op_len,
+                },
+            ));
+        }
+        self.pos += op_len;
+        Ok(Some((field, op)))
+    }
+
+    fn parse_term(&mut self) -> Result<Term, QueryError> {
+        let start = self.pos;
+        let negated = self.peek() == Some('-');
+        if negated {
+            self.pos += 1;
+            if self.peek().is_none_or(char::is_whitespace) {
+                return Err(QueryError::new(
+                    "`-` 后缺少条件",
+                    Span {
+                        start,
+                        end: self.pos,
+                    },
+                ));
+            }
+        }
+
+        let kind = match self.read_field_prefix()? {
+            Some((field, op)) => {
+                let (value, value_span) = self.read_value()?;
+                if value.is_empty() {
+                    return Err(QueryError::new(
+                        "字段缺少取值",
+                        Span {
+                            start,
+                            end: self.pos,
+                        },
+                    ));
+                }
+                TermKind::Field {
+                    field,
+                    op,
+                    value,
+                    value_span,
+                }
+            }
+            None => {
+                let (value, value_span) = self.read_value()?;
+                if value.is_empty() {
+                    return Err(QueryError::new("空的关键词", value_span));
+                }
+                TermKind::Text(value)
+            }
+        };
+
+        Ok(Term {
+            negated,
+            kind,
+            span: Span {
+                start,
+                end: self.pos,
+            },
+        })
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+        let mut terms = Vec::new();
+        loop {
+            parser.skip_ws();
+            if parser.peek().is_none() {
+                break;
+            }
+            terms.push(parser.parse_term()?);
+        }
+        Ok(Self { terms })
+    }
+
+    /// 是否包含需要 Tag 字典解析的字段（module/dept/label）。
+    pub fn needs_tags(&self) -> bool {
+        self.terms.iter().any(|t| {
+            matches!(
+                t.kind,
+                TermKind::Field {
+                    field: Field::Module | Field::Department | Field::Label,
+                    ..
+                }
+            )
+        })
+    }
+
+    /// 按 TagIndex 编译为 SCFilter；名称支持模糊与拼音首字母匹配。
+    pub fn compile(&self, index: &TagIndex) -> Result<SCFilter, QueryError> {
+        let mut filter = SCFilter::new();
+        let mut after: Option<NaiveDateTime> = None;
+        let mut before: Option<NaiveDateTime> = None;
+        let mut time_span: Option<Span> = None;
+
+        for term in &self.terms {
+            match &term.kind {
+                TermKind::Text(text) => {
+                    if term.negated {
+                        filter.exclude_keywords.push(text.clone());
+                    } else if filter.name.is_empty() {
+                        filter.name = text.clone();
+                    } else {
+                        filter.keywords.push(text.clone());
+                    }
+                }
+                TermKind::Field {
+                    field,
+                    op,
+                    value,
+                    value_span,
+                } => match field {
+                    Field::Module => {
+                        let m = resolve(index, TagKind::Module, value, *value_span)?;
+                        let m = Module {
+                            value: m.id,
+                            text: m.name,
+                        };
+                        if term.negated {
+                            filter.exclude_modules.push(m);
+                        } else if filter.module.is_some() {
+                            return Err(QueryError::new("只能指定一个模块", term.span));
+                        } else {
+                            filter.module = Some(m);
+                        }
+                    }
+                    Field::Department => {
+                        let d = resolve(index, TagKind::Department, value, *value_span)?;
+                        let d = index
+                            .department(&d.id)
+                            .expect("resolved department must exist");
+                        if term.negated {
+                            filter.exclude_departments.push(d);
+                        } else if filter.department.is_some() {
+                            return Err(QueryError::new("只能指定一个部门", term.span));
+                        } else {
+                            filter.department = Some(d);
+                        }
+                    }
+                    Field::Label => {
+                        let l = resolve(index, TagKind::Label, value, *value_span)?;
+                        let l = Label {
+                            id: l.id,
+                            name: l.name,
+                        };
+                        if term.negated {
+                            filter.exclude_labels.push(l);
+                        } else {
+                            filter.labels.push(l);
+                        }
+                    }
+                    Field::Status => {
+                        let s = parse_status(value).ok_or_else(|| {
+                            QueryError::new(format!("未知状态 `{}`", value), *value_span)
+                        })?;
+                        if term.negated {
+                            filter.exclude_statuses.push(s);
+                        } else {
+                            filter.statuses.push(s);
+                        }
+                    }
+                    Field::Campus => {
+                        let c = Campus::parse(value).ok_or_else(|| {
+                            QueryError::new(format!("未知校区 `{}`", value), *value_span)
+                        })?;
+                        if term.negated {
+                            filter.exclude_campuses.push(c);
+                        } else {
+                            filter.campuses.push(c);
+                        }
+                    }
+                    Field::Hours => {
+                        let v: f64 = value.parse().map_err(|_| {
+                            QueryError::new(
+                                format!("学时应为数字，而不是 `{}`", value),
+                                *value_span,
+                            )
+                        })?;
+                        let op = op.unwrap_or(CmpOp::Eq);
+                        let op = if term.negated {
+                            op.negate()
+                                .ok_or_else(|| QueryError::new("不支持对 `=` 取反", term.span))?
+                        } else {
+                            op
+                        };
+                        filter = filter.add_hour_bound(op, v);
+                    }
+                    Field::After | Field::Before | Field::On => {
+                        let (lo, hi) = parse_date_bounds(value, *value_span)?;
+                        let (lo, hi) = match (field, term.negated) {
+                            (Field::After, false) | (Field::Before, true) => (Some(lo), None),
+                            (Field::Before, false) | (Field::After, true) => (None, Some(lo)),
+                            (Field::On, false) => (Some(lo), Some(hi)),
+                            _ => return Err(QueryError::new("不支持对 `on:` 取反", term.span)),
+                        };
+                        if let Some(lo) = lo {
+                            after = Some(after.map_or(lo, |a| a.max(lo)));
+                        }
+                        if let Some(hi) = hi {
+                            before = Some(before.map_or(hi, |b| b.min(hi)));
+                        }
+                        time_span = Some(match time_span {
+                            Some(s) => Span {
+                                start: s.start,
+                                end: term.span.end,
+                            },
+                            None => term.span,
+                        });
+                    }
+                },
+            }
+        }
+
+        if after.is_some() || before.is_some() {
+            let start = after.unwrap_or_else(far_past);
+            let end = before.unwrap_or_else(far_future);
+            let period = TimePeriod::from_naive(start, end).map_err(|_| {
+                QueryError::new(
+                    "时间范围为空",
+                    time_span.unwrap_or(Span { start: 0, end: 0 }),
+                )
+            })?;
+            filter.time_period = Some(period);
+            filter.strict_time = true;
+        }
+
+        Ok(filter)
+    }
+}
+
+// ==================== 取值解析 ====================
+
+fn far_past() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1970, 1, 1)
+        .unwrap()
+        .and_time(NaiveTime::MIN)
+}
+
+fn far_future() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(9999, 12, 31)
+        .unwrap()
+        .and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap())
+}
+
+/// 返回 (起点, 当天结束)。只给日期时起点为 00:00。
+fn parse_date_bounds(
+    value: &str,
+    span: Span,
+) -> Result<(NaiveDateTime, NaiveDateTime), QueryError> {
+    let value = value.trim();
+    for fmt in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, fmt) {
+            return Ok((dt, dt));
+        }
+    }
+    for fmt in ["%Y-%m-%d", "%Y/%m/%d"] {
+        if let Ok(d) = NaiveDate::parse_from_str(value, fmt) {
+            let start = d.and_time(NaiveTime::MIN);
+            let end = d.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap());
+            return Ok((start, end));
+        }
+    }
+    Err(QueryError::new(
+        format!("无法解析日期 `{}`，应为 YYYY-MM-DD", value),
+        span,
+    ))
+}
+
+fn parse_status(value: &str) -> Option<Status> {
+    let status = match value.to_ascii_lowercase().as_str() {
+        "applying" | "open" => Status::Applying,
+        "apply_ended" | "closed" => Status::ApplyEnded,
+        "hour_public" | "public" => Status::HourPublic,
+        "hour_append_public" | "append_public" => Status::HourAppendPublic,
+        "public_ended" => Status::PublicEnded,
+        "hour_applying" => Status::HourApplying,
+        "hour_approved" | "approved" => Status::HourApproved,
+        "hour_rejected" | "rejected" => Status::HourRejected,
+        "finished" | "done" => Status::Finished,
+        "abnormal" | "abnormal_finished" => Status::AbnormalFinished,
+        _ => return Status::ALL.iter().copied().find(|s| s.text() == value),
+    };
+    Some(status)
+}
+
+/// 通过 TagIndex 解析名称；无结果或最高分并列时报错并列出候选。
+fn resolve(
+    index: &TagIndex,
+    kind: TagKind,
+    value: &str,
+    span: Span,
+) -> Result<TagMatch, QueryError> {
+    let what = match kind {
+        TagKind::Module => "模块",
+        TagKind::Department => "部门",
+        TagKind::Label => "标签",
+    };
+    index.resolve(kind, value).map_err(|candidates| {
+        if candidates.is_empty() {
+            return QueryError::new(format!("未找到{} `{}`", what, value), span);
+        }
+        let names: Vec<&str> = candidates.iter().take(3).map(|m| m.name.as_str()).collect();
+        QueryError::new(
+            format!(
+                "{} `{}` 有 {} 个匹配：{}{}",
+                what,
+                value,
+                candidates.len(),
+                names.join("、"),
+                if candidates.len() > 3 { " …" } else { "" }
+            ),
+            span,
+        )
+    })
+}
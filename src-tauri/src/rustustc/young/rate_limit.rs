@@ -0,0 +1,89 @@
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ==================== 全局限流 (RateLimit) ====================
+//
+// 后台任务（变更检测、候补、收藏同步等）与前端操作共用同一个令牌桶，
+// 避免短时间内请求过多被二课网限制。
+// 定时报名的预热与报名在 `priority` 内执行：请求不排队等待，但仍计入令牌桶，
+// 之后的普通请求相应多等，不会因为后台轮询耗尽令牌而错过开放时刻。
+
+tokio::task_local! {
+    static PRIORITY: ();
+}
+
+/// 在 `fut` 内发出的请求跳过排队（仍消耗令牌）。
+pub async fn priority<F: Future>(fut: F) -> F::Output {
+    PRIORITY.scope((), fut).await
+}
+
+/// 令牌桶。令牌不足时允许预支（令牌数为负），调用方按返回的时长等待，先到先得。
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    per_sec: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, per_sec: f64, now: Instant) -> Self {
+        Self {
+            capacity: capacity as f64,
+            per_sec,
+            tokens: capacity as f64,
+            updated: now,
+        }
+    }
+
+    /// 取一个令牌，返回需要等待的时长。
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.updated = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.per_sec)
+        }
+    }
+
+    /// 优先请求：取一个令牌但不等待，欠下的令牌由之后的 `reserve` 补足。
+    pub fn take(&mut self, now: Instant) {
+        self.reserve(now);
+    }
+}
+
+pub struct RateLimiter {
+    bucket: Mutex<TokenBucket>,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, per_sec: f64) -> Self {
+        Self {
+            bucket: Mutex::new(TokenBucket::new(capacity, per_sec, Instant::now())),
+        }
+    }
+
+    /// 取一个令牌，必要时等待；在 `priority` 内调用时不等待。
+    pub async fn acquire(&self) {
+        let priority = PRIORITY.try_with(|_| ()).is_ok();
+        let wait = match self.bucket.lock() {
+            Ok(mut bucket) if priority => {
+                bucket.take(Instant::now());
+                Duration::ZERO
+            }
+            Ok(mut bucket) => bucket.reserve(Instant::now()),
+            Err(_) => Duration::ZERO,
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// 所有 `YouthService` 请求共用：突发 5 个，之后每秒 4 个。
+pub static GLOBAL: Lazy<RateLimiter> = Lazy::new(|| RateLimiter::new(5, 4.0));
@@ -0,0 +1,123 @@
+use crate::rustustc::young::model::{cst_serde, CstDateTime, SecondClass};
+use anyhow::{bail, Result};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+// ==================== 活动提醒 (Reminder) ====================
+//
+// 由已报名活动列表生成提醒：在 `hold_time().start` 前若干分钟（默认 1 天与 1 小时）通知，
+// 内容包含地点。报名、取消或时间变更后重新生成，已发送的提醒按 key 去重。
+
+/// 最多配置的提醒数
+pub const MAX_OFFSETS: usize = 5;
+/// 最早提前 7 天
+pub const MAX_OFFSET_MINUTES: u32 = 7 * 24 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReminderSettings {
+    pub enabled: bool,
+    /// 活动开始前多少分钟提醒
+    pub offsets_minutes: Vec<u32>,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            offsets_minutes: vec![24 * 60, 60],
+        }
+    }
+}
+
+impl ReminderSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.offsets_minutes.len() > MAX_OFFSETS {
+            bail!("At most {} reminders are allowed", MAX_OFFSETS);
+        }
+        if self
+            .offsets_minutes
+            .iter()
+            .any(|m| *m == 0 || *m > MAX_OFFSET_MINUTES)
+        {
+            bail!("Reminder offsets must be between 1 minute and 7 days");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub activity_id: String,
+    pub activity_name: String,
+    pub place: Option<String>,
+    #[serde(with = "cst_serde")]
+    pub start: CstDateTime,
+    pub offset_minutes: u32,
+    #[serde(with = "cst_serde")]
+    pub fire_at: CstDateTime,
+}
+
+fn offset_text(minutes: u32) -> String {
+    match minutes {
+        m if m % (24 * 60) == 0 => format!("{} 天后", m / (24 * 60)),
+        m if m % 60 == 0 => format!("{} 小时后", m / 60),
+        m => format!("{} 分钟后", m),
+    }
+}
+
+impl Reminder {
+    /// 去重用的 key；活动时间变更后 key 随之变化，会重新提醒。
+    pub fn key(&self) -> String {
+        format!(
+            "{}@{}-{}",
+            self.activity_id,
+            self.start.timestamp(),
+            self.offset_minutes
+        )
+    }
+
+    pub fn message(&self) -> String {
+        let place = self
+            .place
+            .as_deref()
+            .map(|p| format!("，地点：{}", p))
+            .unwrap_or_default();
+        format!(
+            "「{}」将于{}（{}）开始{}",
+            self.activity_name,
+            offset_text(self.offset_minutes),
+            self.start.format("%m-%d %H:%M"),
+            place
+        )
+    }
+}
+
+/// 为尚未开始的活动生成提醒，只保留提醒时间在 `now` 之后且未发送过的，按提醒时间排序。
+pub fn plan(
+    registered: &[SecondClass],
+    settings: &ReminderSettings,
+    fired: &[String],
+    now: CstDateTime,
+) -> Vec<Reminder> {
+    if !settings.enabled {
+        return Vec::new();
+    }
+    let mut out: Vec<Reminder> = registered
+        .iter()
+        .filter_map(|sc| Some((sc, sc.hold_time().ok()?.start)))
+        .filter(|(_, start)| *start > now)
+        .flat_map(|(sc, start)| {
+            settings.offsets_minutes.iter().map(move |&m| Reminder {
+                activity_id: sc.id.clone(),
+                activity_name: sc.name.clone(),
+                place: sc.place(),
+                start,
+                offset_minutes: m,
+                fire_at: start - Duration::minutes(m as i64),
+            })
+        })
+        .filter(|r| r.fire_at > now && !fired.contains(&r.key()))
+        .collect();
+    out.sort_by_key(|r| r.fire_at);
+    out
+}
@@ -0,0 +1,65 @@
+use crate::rustustc::young::model::{cst_serde, CstDateTime, SCFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// ==================== 保存的搜索 (SavedSearch) ====================
+//
+// 保存查询语句及编译后的 SCFilter，后台定期执行。每个搜索记住已见过的活动 ID，
+// 只对新出现的匹配发送通知；「全部标为已读」后新匹配并入已见集合。
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    /// 新建时留空，由保存时生成
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// 原始查询语句，便于编辑
+    pub query: String,
+    pub filter: SCFilter,
+    #[serde(default = "default_notify")]
+    pub notify: bool,
+    /// 已读的匹配
+    #[serde(default)]
+    pub seen: BTreeSet<String>,
+    /// 尚未标为已读的新匹配，按发现顺序
+    #[serde(default)]
+    pub unseen: Vec<String>,
+    #[serde(with = "cst_serde::option", default)]
+    pub last_run_at: Option<CstDateTime>,
+}
+
+fn default_notify() -> bool {
+    true
+}
+
+impl SavedSearch {
+    /// 记录一次执行结果，返回本次新出现的活动 ID。
+    ///
+    /// 第一次执行只建立基线（全部视为已读），避免刚保存就收到大量通知。
+    /// 不再出现在结果中的已读 ID 会被清理，防止集合无限增长。
+    pub fn record(&mut self, ids: &[String], now: CstDateTime) -> Vec<String> {
+        let first_run = self.last_run_at.is_none();
+        self.last_run_at = Some(now);
+        if first_run {
+            self.seen = ids.iter().cloned().collect();
+            self.unseen.clear();
+            return Vec::new();
+        }
+
+        let current: BTreeSet<&String> = ids.iter().collect();
+        self.seen.retain(|id| current.contains(id));
+        self.unseen.retain(|id| current.contains(id));
+
+        let new: Vec<String> = ids
+            .iter()
+            .filter(|id| !self.seen.contains(*id) && !self.unseen.contains(id))
+            .cloned()
+            .collect();
+        self.unseen.extend(new.iter().cloned());
+        new
+    }
+
+    pub fn mark_all_seen(&mut self) {
+        self.seen.extend(self.unseen.drain(..));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::rustustc::young::model::{now, CstDateTime, SecondClass, Status};
+use crate::rustustc::young::service::YouthService;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+// ==================== 系列活动 (Series) ====================
+//
+// 系列活动（itemCategory == "1"）的子项目只能通过 selectSignChirdItem 拿到扁平列表。
+// Series 在此基础上汇总：各状态数量、已报名/已参加场次、学时合计与下一场。
+
+/// 展开子项目详情时的最大并发数
+const EXPAND_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesSession {
+    pub activity: SecondClass,
+    /// 出现在已参与列表中，或详情中 `booleanRegistration == 1`
+    pub registered: bool,
+    /// 举办时间已结束
+    pub ended: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusCount {
+    pub status: Status,
+    pub code: i32,
+    pub text: &'static str,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Series {
+    pub parent: SecondClass,
+    /// 按举办开始时间排序，无法解析时间的排在最后
+    pub sessions: Vec<SeriesSession>,
+    pub status_counts: Vec<StatusCount>,
+    pub total: usize,
+    /// 已报名的场次
+    pub registered: usize,
+    /// 已报名且已结束的场次
+    pub attended: usize,
+    /// 仍可报名的场次
+    pub open: usize,
+    pub total_hours: f64,
+    pub registered_hours: f64,
+    /// 尚未开始的最近一场
+    pub next_session: Option<SecondClass>,
+}
+
+impl Series {
+    /// 由父项目、子项目与已参与列表汇总（纯计算，不访问网络）。
+    pub fn build(
+        parent: SecondClass,
+        children: Vec<SecondClass>,
+        participated: &[SecondClass],
+        now: CstDateTime,
+    ) -> Self {
+        let joined: HashSet<&str> = participated.iter().map(|p| p.id.as_str()).collect();
+
+        let mut sessions: Vec<SeriesSession> = children
+            .into_iter()
+            .map(|c| {
+                let ended = c.hold_time().map(|t| t.end < now).unwrap_or(false);
+                SeriesSession {
+                    registered: c.applied() || joined.contains(c.id.as_str()),
+                    ended,
+                    activity: c,
+                }
+            })
+            .collect();
+        sessions.sort_by_key(|s| {
+            let start = s.activity.hold_time().ok().map(|t| t.start);
+            (start.is_none(), start)
+        });
+
+        let mut status_counts: Vec<StatusCount> = Vec::new();
+        for s in &sessions {
+            let status = s.activity.status();
+            match status_counts.iter_mut().find(|c| c.status == status) {
+                Some(c) => c.count += 1,
+                None => status_counts.push(StatusCount {
+                    status,
+                    code: status.code(),
+                    text: status.text(),
+                    count: 1,
+                }),
+            }
+        }
+
+        let hours = |s: &SeriesSession| s.activity.hours().unwrap_or(0.0);
+        let next_session = sessions
+            .iter()
+            .find(|s| {
+                s.activity
+                    .hold_time()
+                    .map(|t| t.start > now)
+                    .unwrap_or(false)
+            })
+            .map(|s| s.activity.clone());
+
+        Self {
+            total: sessions.len(),
+            registered: sessions.iter().filter(|s| s.registered).count(),
+            attended: sessions.iter().filter(|s| s.registered && s.ended).count(),
+            open: sessions.iter().filter(|s| s.activity.applyable()).count(),
+            total_hours: sessions.iter().map(hours).sum(),
+            registered_hours: sessions.iter().filter(|s| s.registered).map(hours).sum(),
+            next_session,
+            status_counts,
+            sessions,
+            parent,
+        }
+    }
+
+    /// 拉取子项目并并发展开详情（报名人数等字段只在 `queryById` 中返回），再与已参与列表汇总。
+    /// 单个子项目详情获取失败时保留列表中的数据。
+    pub async fn load(service: &Arc<YouthService>, parent: SecondClass) -> Result<Self> {
+        let (children, participated) = tokio::try_join!(
+            parent.get_children(service),
+            SecondClass::get_participated(service)
+        )?;
+
+        let semaphore = Arc::new(Semaphore::new(EXPAND_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (i, child) in children.iter().enumerate() {
+            let service = service.clone();
+            let semaphore = semaphore.clone();
+            let mut child = child.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let res = child.update(&service).await;
+                (i, res.map(|_| child))
+            });
+        }
+
+        let mut expanded = children;
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok((i, Ok(child))) = joined {
+                expanded[i] = child;
+            }
+        }
+
+        Ok(Self::build(parent, expanded, &participated, now()))
+    }
+}
@@ -3,19 +3,48 @@ use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose, Engine as _};
 use cbc::cipher::{BlockEncryptMut, KeyIvInit};
 use cbc::Encryptor;
+use chrono::DateTime;
 use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri_plugin_http::reqwest::Url;
+use tauri_plugin_http::reqwest::{header, multipart, Url};
 
 use crate::rustustc::cas::client::CASClient;
+use crate::rustustc::error::YouthApiError;
 use crate::rustustc::url::generate_url;
+use crate::rustustc::young::rate_limit;
 
 type Aes128CbcEnc = Encryptor<Aes128>;
 
+/// 参与平均的时钟偏差样本数
+const SKEW_SAMPLES: usize = 8;
+
+type ExpiredHook = Box<dyn Fn(&str) + Send + Sync>;
+
 pub struct YouthService {
     access_token: String,
     client: tauri_plugin_http::reqwest::Client,
     pub retry: u32,
+    /// 最近若干次响应 `Date` 头估计的时钟偏差（服务器 − 本地，毫秒）
+    skew_samples: Mutex<VecDeque<i64>>,
+    /// 接口返回 token 失效时调用，参数为接口原文
+    on_expired: OnceLock<ExpiredHook>,
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// 由响应 `Date` 头估计时钟偏差（服务器 − 本地，毫秒）。
+/// `Date` 只精确到秒，取该秒的中点，并以请求往返的中点作为服务器生成响应的本地时刻。
+pub fn skew_from_date(date: &str, sent_ms: i64, received_ms: i64) -> Option<i64> {
+    let server = DateTime::parse_from_rfc2822(date.trim()).ok()?;
+    let server_ms = server.timestamp_millis() + 500;
+    Some(server_ms - (sent_ms + received_ms) / 2)
 }
 
 impl YouthService {
@@ -54,7 +83,7 @@ impl YouthService {
             serde_json::from_str(&res_text).context("Failed to parse Youth login JSON")?;
 
         if !res["success"].as_bool().unwrap_or(false) {
-            bail!("Youth login failed: {}", res["message"]);
+            return Err(YouthApiError::from_response(&res).into());
         }
 
         let token = res["result"]["token"]
@@ -66,9 +95,50 @@ impl YouthService {
             access_token: token,
             client: cas_client.client_ref().clone(),
             retry: 3, // 默认重试3次
+            skew_samples: Mutex::new(VecDeque::with_capacity(SKEW_SAMPLES)),
+            on_expired: OnceLock::new(),
         })
     }
 
+    /// 设置 token 失效时的回调（只能设置一次），命令与后台任务的请求都会触发。
+    pub fn on_session_expired(&self, hook: impl Fn(&str) + Send + Sync + 'static) {
+        let _ = self.on_expired.set(Box::new(hook));
+    }
+
+    /// 接口返回 `success=false` 时的错误；token 失效时先通知回调。
+    fn reject(&self, resp: &Value) -> anyhow::Error {
+        let err = YouthApiError::from_response(resp);
+        if err.is_session_expired() {
+            if let Some(hook) = self.on_expired.get() {
+                hook(&err.message);
+            }
+        }
+        err.into()
+    }
+
+    /// 估计的服务器时钟偏差（服务器 − 本地，毫秒）；尚无响应时为 `None`。
+    pub fn clock_skew(&self) -> Option<i64> {
+        let samples = self.skew_samples.lock().ok()?;
+        if samples.is_empty() {
+            return None;
+        }
+        Some(samples.iter().sum::<i64>() / samples.len() as i64)
+    }
+
+    fn record_skew(&self, resp: &tauri_plugin_http::reqwest::Response, sent_ms: i64) {
+        let skew = resp
+            .headers()
+            .get(header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|d| skew_from_date(d, sent_ms, now_millis()));
+        if let (Some(skew), Ok(mut samples)) = (skew, self.skew_samples.lock()) {
+            if samples.len() == SKEW_SAMPLES {
+                samples.pop_front();
+            }
+            samples.push_back(skew);
+        }
+    }
+
     fn encrypt(&self, data: &Value, timestamp: u64) -> Result<String> {
         // Token 末尾 32 字符被切分为 key+iv（各 16 字节）；若后端变更 token 长度，这里将 panic，建议未来显式校验长度并返回友好错误。
         let token_len = self.access_token.len();
@@ -150,10 +220,13 @@ impl YouthService {
             _ => bail!("Unsupported method"),
         };
 
+        rate_limit::GLOBAL.acquire().await;
+        let sent_ms = now_millis();
         let resp = req
             .header("X-Access-Token", &self.access_token)
             .send()
             .await?;
+        self.record_skew(&resp, sent_ms);
         let resp_bytes = resp.bytes().await?;
         let resp_text = String::from_utf8(resp_bytes.to_vec())?;
         let resp_json: Value = serde_json::from_str(&resp_text)?;
@@ -161,8 +234,40 @@ impl YouthService {
         if resp_json["success"].as_bool().unwrap_or(false) {
             Ok(resp_json["result"].clone())
         } else {
-            bail!("API Error: {}", resp_json["message"]);
+            Err(self.reject(&resp_json))
+        }
+    }
+
+    /// 以 multipart 上传文件（字段名 `file`），返回服务器存储路径（如 `group1/M00/...`）。
+    /// 上传接口不加密参数，仅校验 `X-Access-Token`。
+    pub async fn upload(&self, endpoint: &str, file_name: &str, bytes: Vec<u8>) -> Result<String> {
+        let url = generate_url(
+            "young",
+            &format!("login/wisdom-group-learning-bg/{}", endpoint),
+        );
+        let part = multipart::Part::bytes(bytes).file_name(file_name.to_string());
+        let form = multipart::Form::new().part("file", part);
+
+        rate_limit::GLOBAL.acquire().await;
+        let resp = self
+            .client
+            .post(&url)
+            .header("X-Access-Token", &self.access_token)
+            .multipart(form)
+            .send()
+            .await?;
+        let resp_json: Value = serde_json::from_slice(&resp.bytes().await?)?;
+
+        if !resp_json["success"].as_bool().unwrap_or(false) {
+            return Err(self.reject(&resp_json));
         }
+        // 存储路径可能在 result 中，也可能在 message 中
+        resp_json["result"]
+            .as_str()
+            .or_else(|| resp_json["message"].as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .context("Upload response missing file path")
     }
 
     pub async fn get_result(&self, url: &str, params: Option<Value>) -> Result<Value> {
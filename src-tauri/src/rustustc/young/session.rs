@@ -0,0 +1,75 @@
+use crate::rustustc::error::AppError;
+use serde::Serialize;
+
+// ==================== 会话状态 (SessionState) ====================
+//
+// 登录状态机的纯逻辑：各状态下会话是否可用、失效与刷新结果对应的下一个状态。
+// 状态的保存、加锁与事件通知由 `crate::session::SessionManager` 负责。
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SessionState {
+    LoggedOut,
+    LoggingIn {
+        username: String,
+    },
+    Active {
+        username: String,
+    },
+    /// 刷新期间旧会话仍可使用
+    Refreshing {
+        username: String,
+    },
+    Expired {
+        username: String,
+        reason: String,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl SessionState {
+    /// 会话可用（`Active` 或 `Refreshing`）。
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Self::Active { .. } | Self::Refreshing { .. })
+    }
+
+    /// 同一账号已登录时可以复用会话（仍需验证 token）。
+    pub fn can_reuse(&self, username: &str) -> bool {
+        matches!(self, Self::Active { username: u } if u == username)
+    }
+
+    /// 会话不可用时命令返回的错误：失效为 `SessionExpired`，其余为 `AuthRequired`。
+    pub fn unavailable_error(&self) -> AppError {
+        match self {
+            Self::Expired { reason, .. } => AppError::SessionExpired(reason.clone()),
+            _ => AppError::AuthRequired,
+        }
+    }
+
+    /// token 失效后的状态；只有可用的会话会转为 `Expired`。
+    pub fn expire(&self, reason: &str) -> Option<Self> {
+        match self {
+            Self::Active { username } | Self::Refreshing { username } => Some(Self::Expired {
+                username: username.clone(),
+                reason: reason.to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 刷新结束后的状态：成功为 `Active`；网络错误回到刷新前的状态；其余为 `Expired`。
+    pub fn after_refresh(previous: Self, username: &str, result: Result<(), &AppError>) -> Self {
+        match result {
+            Ok(()) => Self::Active {
+                username: username.to_string(),
+            },
+            Err(e) if e.retryable() => previous,
+            Err(e) => Self::Expired {
+                username: username.to_string(),
+                reason: e.message().to_string(),
+            },
+        }
+    }
+}
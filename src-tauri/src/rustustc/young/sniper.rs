@@ -0,0 +1,107 @@
+use crate::rustustc::young::model::{cst_serde, CstDateTime, SecondClass};
+use anyhow::{bail, Context, Result};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+// ==================== 定时报名 (Sniper) ====================
+//
+// 热门活动在 `applySt` 后几秒内报满。定时任务在开放前 `PREWARM_LEAD_SECS` 秒预热
+// （校验登录、预取报名信息），然后在服务器时间到达 `applySt` 的瞬间调用 `apply`。
+// 本地时钟与服务器的偏差由 `YouthService::clock_skew` 给出。
+
+/// 提前预热的秒数
+pub const PREWARM_LEAD_SECS: i64 = 60;
+/// 最多尝试次数（含第一次）
+pub const MAX_ATTEMPTS: u32 = 5;
+/// 两次尝试之间的间隔
+pub const RETRY_DELAY_MS: u64 = 300;
+/// 错过开放时间超过该时长的任务不再执行（如应用重启时）
+pub const MISSED_GRACE_MINUTES: i64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum JobState {
+    Scheduled,
+    Warming,
+    Applying,
+    Succeeded,
+    Failed { message: String },
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SniperJob {
+    pub activity_id: String,
+    pub activity_name: String,
+    /// 报名开放时间（服务器时间）
+    #[serde(with = "cst_serde")]
+    pub open_at: CstDateTime,
+    pub profile_id: Option<String>,
+    pub auto_cancel: bool,
+    pub state: JobState,
+    pub attempts: u32,
+    #[serde(with = "cst_serde")]
+    pub created_at: CstDateTime,
+}
+
+impl SniperJob {
+    /// 为尚未开放报名的活动创建任务。已报名、报名已开放或没有报名时间时报错。
+    pub fn new(
+        sc: &SecondClass,
+        profile_id: Option<String>,
+        auto_cancel: bool,
+        now: CstDateTime,
+    ) -> Result<Self> {
+        if sc.applied() {
+            bail!("已报名该活动");
+        }
+        let apply = sc.apply_time().context("活动没有报名时间")?;
+        if apply.start <= now {
+            bail!("报名已开放，请直接报名");
+        }
+        Ok(Self {
+            activity_id: sc.id.clone(),
+            activity_name: sc.name.clone(),
+            open_at: apply.start,
+            profile_id,
+            auto_cancel,
+            state: JobState::Scheduled,
+            attempts: 0,
+            created_at: now,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.state,
+            JobState::Scheduled | JobState::Warming | JobState::Applying
+        )
+    }
+
+    /// 服务器时间到达 `open_at` 时对应的本地时间。
+    pub fn fire_at(&self, skew_ms: Option<i64>) -> CstDateTime {
+        self.open_at - Duration::milliseconds(skew_ms.unwrap_or(0))
+    }
+
+    pub fn prewarm_at(&self, skew_ms: Option<i64>) -> CstDateTime {
+        self.fire_at(skew_ms) - Duration::seconds(PREWARM_LEAD_SECS)
+    }
+
+    /// 应用重启后，已错过开放时间太久的任务不再执行。
+    pub fn missed(&self, now: CstDateTime) -> bool {
+        now - self.open_at > Duration::minutes(MISSED_GRACE_MINUTES)
+    }
+}
+
+/// 报名失败后是否值得重试：名额已满、已报名、时间冲突、资格不符等确定性错误不重试。
+pub fn should_retry(message: &str, attempts: u32) -> bool {
+    const FINAL: [&str; 6] = [
+        "已报名",
+        "已满",
+        "名额",
+        "时间冲突",
+        "不符合",
+        "不在报名范围",
+    ];
+    attempts < MAX_ATTEMPTS && !FINAL.iter().any(|k| message.contains(k))
+}
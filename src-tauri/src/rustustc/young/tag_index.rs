@@ -0,0 +1,324 @@
+use crate::rustustc::young::model::{Department, Label, Module, Tag};
+use crate::rustustc::young::service::YouthService;
+use anyhow::Result;
+use pinyin::ToPinyin;
+use serde::{Deserialize, Serialize};
+
+// ==================== TagIndex ====================
+//
+// 一次性加载 Module / Department / Label 字典，提供：
+// - 精确解析：按文本、value 或 ID
+// - 模糊搜索：前缀、包含、子序列，以及中文名称的拼音首字母（`gqxh` → 学生正则古琴协会）
+// - 部门从根节点起的完整路径
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagKind {
+    Module,
+    Department,
+    Label,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Contains,
+    Initials,
+    Fuzzy,
+}
+
+/// 搜索结果。`id` 对 Module 而言是 `value`。
+#[derive(Debug, Clone, Serialize)]
+pub struct TagMatch {
+    pub kind: TagKind,
+    pub id: String,
+    pub name: String,
+    /// 部门为从根到自身的名称路径，其余为 `[name]`
+    pub path: Vec<String>,
+    pub matched_by: MatchKind,
+    pub score: u32,
+}
+
+/// 字典条目，供选择器使用。
+#[derive(Debug, Clone, Serialize)]
+pub struct TagEntry {
+    pub kind: TagKind,
+    pub id: String,
+    pub name: String,
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    kind: TagKind,
+    id: String,
+    name: String,
+    lower: String,
+    initials: String,
+    /// 部门：父节点在 `entries` 中的下标
+    parent: Option<usize>,
+    level: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TagIndex {
+    modules: Vec<Module>,
+    labels: Vec<Label>,
+    roots: Vec<Department>,
+    entries: Vec<Entry>,
+}
+
+/// 名称的拼音首字母串：汉字取首字母，ASCII 字母数字保留（小写），其余忽略。
+pub fn pinyin_initials(s: &str) -> String {
+    s.chars()
+        .filter_map(|c| {
+            if c.is_ascii_alphanumeric() {
+                Some(c.to_ascii_lowercase())
+            } else {
+                c.to_pinyin().and_then(|p| p.first_letter().chars().next())
+            }
+        })
+        .collect()
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut hay = haystack.chars();
+    needle.chars().all(|n| hay.any(|h| h == n))
+}
+
+impl TagIndex {
+    pub fn new(modules: Vec<Module>, departments: Vec<Department>, labels: Vec<Label>) -> Self {
+        let mut entries = Vec::new();
+        for m in &modules {
+            entries.push(Entry::new(TagKind::Module, &m.value, &m.text, None, 0));
+        }
+        for l in &labels {
+            entries.push(Entry::new(TagKind::Label, &l.id, &l.name, None, 0));
+        }
+        fn walk(dept: &Department, parent: Option<usize>, entries: &mut Vec<Entry>) {
+            let idx = entries.len();
+            entries.push(Entry::new(
+                TagKind::Department,
+                &dept.id,
+                &dept.name,
+                parent,
+                dept.level,
+            ));
+            for child in &dept.children {
+                walk(child, Some(idx), entries);
+            }
+        }
+        for root in &departments {
+            walk(root, None, &mut entries);
+        }
+        Self {
+            modules,
+            labels,
+            roots: departments,
+            entries,
+        }
+    }
+
+    pub async fn load(service: &YouthService) -> Result<Self> {
+        let modules = Module::get_available_tags(service).await?;
+        let departments = Department::get_available_tags(service).await?;
+        let labels = Label::get_available_tags(service).await?;
+        Ok(Self::new(modules, departments, labels))
+    }
+
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// 部门树的根节点列表（含 children）。
+    pub fn departments(&self) -> &[Department] {
+        &self.roots
+    }
+
+    /// 按 `text` 或 `value`（忽略大小写）解析模块。
+    pub fn module(&self, key: &str) -> Option<&Module> {
+        self.modules
+            .iter()
+            .find(|m| m.text == key || m.value.eq_ignore_ascii_case(key))
+    }
+
+    /// 按 ID 或名称解析标签。
+    pub fn label(&self, key: &str) -> Option<&Label> {
+        self.labels.iter().find(|l| l.id == key || l.name == key)
+    }
+
+    /// 按 ID 或完整名称解析部门（不含 children）。
+    pub fn department(&self, key: &str) -> Option<Department> {
+        let idx = self
+            .entries
+            .iter()
+            .position(|e| e.kind == TagKind::Department && (e.id == key || e.name == key))?;
+        Some(self.entries[idx].to_department())
+    }
+
+    /// 从根到该部门的路径（含自身）；ID 不存在时返回 `None`。
+    pub fn department_path(&self, id: &str) -> Option<Vec<Department>> {
+        let idx = self
+            .entries
+            .iter()
+            .position(|e| e.kind == TagKind::Department && e.id == id)?;
+        Some(
+            self.path_indices(idx)
+                .into_iter()
+                .map(|i| self.entries[i].to_department())
+                .collect(),
+        )
+    }
+
+    /// 某类字典的全部条目（部门按树的先序排列）。
+    pub fn entries(&self, kind: TagKind) -> Vec<TagEntry> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.kind == kind)
+            .map(|(i, e)| TagEntry {
+                kind: e.kind,
+                id: e.id.clone(),
+                name: e.name.clone(),
+                path: self.path_names(i),
+            })
+            .collect()
+    }
+
+    fn path_names(&self, idx: usize) -> Vec<String> {
+        self.path_indices(idx)
+            .into_iter()
+            .map(|p| self.entries[p].name.clone())
+            .collect()
+    }
+
+    fn path_indices(&self, idx: usize) -> Vec<usize> {
+        let mut path = vec![idx];
+        let mut cur = idx;
+        while let Some(p) = self.entries[cur].parent {
+            path.push(p);
+            cur = p;
+        }
+        path.reverse();
+        path
+    }
+
+    /// 模糊搜索，按得分降序、名称长度升序排列。`kind` 为空时搜索全部字典。
+    pub fn search(&self, kind: Option<TagKind>, query: &str, limit: usize) -> Vec<TagMatch> {
+        let query = query.trim();
+        if query.is_empty() {
+            return vec![];
+        }
+        let lower = query.to_lowercase();
+
+        let mut matches: Vec<(u32, MatchKind, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| kind.is_none_or(|k| e.kind == k))
+            .filter_map(|(i, e)| e.score(query, &lower).map(|(s, m)| (s, m, i)))
+            .collect();
+        matches.sort_by(|a, b| {
+            b.0.cmp(&a.0).then_with(|| {
+                let (ea, eb) = (&self.entries[a.2], &self.entries[b.2]);
+                ea.name
+                    .chars()
+                    .count()
+                    .cmp(&eb.name.chars().count())
+                    .then(ea.level.cmp(&eb.level))
+            })
+        });
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(score, matched_by, i)| {
+                let e = &self.entries[i];
+                TagMatch {
+                    kind: e.kind,
+                    id: e.id.clone(),
+                    name: e.name.clone(),
+                    path: self.path_names(i),
+                    matched_by,
+                    score,
+                }
+            })
+            .collect()
+    }
+
+    /// 精确解析，失败后退回模糊搜索；最高分不唯一时视为有歧义，返回全部并列候选。
+    pub fn resolve(&self, kind: TagKind, key: &str) -> Result<TagMatch, Vec<TagMatch>> {
+        let mut found = self.search(Some(kind), key, 8);
+        match found.first() {
+            None => Err(vec![]),
+            Some(top) => {
+                let top_score = top.score;
+                let tied = found.iter().filter(|m| m.score == top_score).count();
+                if tied == 1 {
+                    Ok(found.swap_remove(0))
+                } else {
+                    found.truncate(tied);
+                    Err(found)
+                }
+            }
+        }
+    }
+}
+
+impl Entry {
+    fn new(kind: TagKind, id: &str, name: &str, parent: Option<usize>, level: i32) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            name: name.to_string(),
+            lower: name.to_lowercase(),
+            initials: pinyin_initials(name),
+            parent,
+            level,
+        }
+    }
+
+    fn to_department(&self) -> Department {
+        Department {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            children: vec![],
+            level: self.level,
+        }
+    }
+
+    fn score(&self, query: &str, lower: &str) -> Option<(u32, MatchKind)> {
+        if self.name == query || self.id == query || self.id.eq_ignore_ascii_case(query) {
+            return Some((100, MatchKind::Exact));
+        }
+        if self.lower.starts_with(lower) {
+            return Some((80, MatchKind::Prefix));
+        }
+        if self.lower.contains(lower) {
+            return Some((60, MatchKind::Contains));
+        }
+        // 拼音首字母只对纯 ASCII 查询有意义
+        if query.is_ascii() && !self.initials.is_empty() {
+            if self.initials == lower {
+                return Some((70, MatchKind::Initials));
+            }
+            if self.initials.starts_with(lower) {
+                return Some((55, MatchKind::Initials));
+            }
+            if self.initials.contains(lower) {
+                return Some((45, MatchKind::Initials));
+            }
+        }
+        if is_subsequence(lower, &self.lower) {
+            let ratio = lower.chars().count() * 20 / self.lower.chars().count().max(1);
+            return Some((20 + ratio as u32, MatchKind::Fuzzy));
+        }
+        None
+    }
+}
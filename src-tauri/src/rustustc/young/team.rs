@@ -0,0 +1,270 @@
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::SecondClass;
+use crate::rustustc::young::service::YouthService;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+// ==================== 组队报名 (Team) ====================
+//
+// `teamSize` 非空的活动以队伍为单位报名：
+// - `teamSize`：每队最多人数（含队长）
+// - `itemLimitNum`：每队最少人数
+// - `teamNum` / `applyTeamNum`：队伍数上限 / 已报名队伍数
+// 人数与队伍数在本地先校验，再调用接口；校验失败为 `TEAM_INVALID`，非组队活动为 `NOT_A_TEAM_ACTIVITY`。
+//
+// 成员、队长统一用用户 ID（`User.id`，即 `leaderId` / 成员的 `userId`）标识，不用学号；
+// command 层传入的都是 `User::get_current` / `User::find` 得到的 `id`。
+//
+// 未经验证：`itemLimitNum` 表示每队最少人数只是推测；`item/scTeam/myTeam|queryByCode` 与
+// `mobile/item/team/create/{id}|join|quit/{id}|dissolve/{id}` 的路径、字段名（`teamName`、
+// `memberIds`、`leaderId`、`userId`、`teamCode`）按其他二课接口的命名推测，尚未用真实账号抓包确认。
+
+const MY_TEAM_URL: &str = "item/scTeam/myTeam";
+const BY_CODE_URL: &str = "item/scTeam/queryByCode";
+
+fn team_invalid(e: impl ToString) -> AppError {
+    AppError::rule("TEAM_INVALID", e)
+}
+
+fn raw_i64(v: &Value, key: &str) -> Option<i64> {
+    match v.get(key)? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TeamLimits {
+    pub min_size: u32,
+    pub max_size: u32,
+    pub max_teams: Option<u32>,
+    pub applied_teams: u32,
+}
+
+impl TeamLimits {
+    /// 非组队活动返回 `None`。
+    pub fn from_activity(sc: &SecondClass) -> Option<Self> {
+        let max_size = raw_i64(&sc.raw, "teamSize").filter(|n| *n > 0)? as u32;
+        let min_size = raw_i64(&sc.raw, "itemLimitNum")
+            .filter(|n| *n > 0)
+            .map(|n| (n as u32).min(max_size))
+            .unwrap_or(1);
+        Some(Self {
+            min_size,
+            max_size,
+            max_teams: raw_i64(&sc.raw, "teamNum")
+                .filter(|n| *n > 0)
+                .map(|n| n as u32),
+            applied_teams: raw_i64(&sc.raw, "applyTeamNum").unwrap_or(0).max(0) as u32,
+        })
+    }
+
+    /// 同 `from_activity`，非组队活动返回 `NOT_A_TEAM_ACTIVITY`。
+    pub fn require(sc: &SecondClass) -> Result<Self> {
+        match Self::from_activity(sc) {
+            Some(limits) => Ok(limits),
+            None => bail!(AppError::rule(
+                "NOT_A_TEAM_ACTIVITY",
+                "The specified activity does not take team registrations.",
+            )),
+        }
+    }
+
+    pub fn teams_full(&self) -> bool {
+        self.max_teams.is_some_and(|m| self.applied_teams >= m)
+    }
+
+    /// 建队：成员（不含队长）去重、不能包含队长本人，总人数在 `[min_size, max_size]` 内。
+    pub fn check_create(&self, leader_id: &str, member_ids: &[String]) -> Result<()> {
+        if self.teams_full() {
+            bail!("队伍数已达上限");
+        }
+        let mut seen = HashSet::new();
+        for id in member_ids {
+            if id == leader_id {
+                bail!("成员中不能包含队长本人");
+            }
+            if !seen.insert(id.as_str()) {
+                bail!("成员 {} 重复", id);
+            }
+        }
+        let size = member_ids.len() as u32 + 1;
+        if size < self.min_size {
+            bail!("队伍至少需要 {} 人", self.min_size);
+        }
+        if size > self.max_size {
+            bail!("队伍最多 {} 人", self.max_size);
+        }
+        Ok(())
+    }
+
+    /// 加入已有队伍：队伍未满且自己不在队中。
+    pub fn check_join(&self, team: &Team, user_id: &str) -> Result<()> {
+        if team.members.iter().any(|m| m.id == user_id) {
+            bail!("已在该队伍中");
+        }
+        if team.members.len() as u32 >= self.max_size {
+            bail!("队伍已满（{} 人）", self.max_size);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RawTeamMember")]
+pub struct TeamMember {
+    /// 用户 ID（同 `User.id`）
+    pub id: String,
+    /// 学号
+    pub username: Option<String>,
+    pub name: String,
+    pub is_leader: bool,
+}
+
+/// 成员记录可能同时带关系记录自身的 `id` 与用户的 `userId`，以 `userId` 为准。
+#[derive(Deserialize)]
+struct RawTeamMember {
+    id: Option<String>,
+    #[serde(rename = "userId")]
+    user_id: Option<String>,
+    username: Option<String>,
+    #[serde(alias = "realname")]
+    name: String,
+    #[serde(alias = "isLeader", default)]
+    is_leader: bool,
+}
+
+impl From<RawTeamMember> for TeamMember {
+    fn from(raw: RawTeamMember) -> Self {
+        Self {
+            id: raw.user_id.or(raw.id).unwrap_or_default(),
+            username: raw.username,
+            name: raw.name,
+            is_leader: raw.is_leader,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub id: String,
+    #[serde(alias = "teamName")]
+    pub name: String,
+    #[serde(alias = "itemId")]
+    pub item_id: Option<String>,
+    /// 邀请码
+    #[serde(alias = "teamCode", alias = "code")]
+    pub invite_code: Option<String>,
+    #[serde(alias = "leaderId")]
+    pub leader_id: Option<String>,
+    #[serde(default)]
+    pub members: Vec<TeamMember>,
+}
+
+impl Team {
+    fn from_result(data: Value) -> Result<Option<Self>> {
+        if data.is_null() {
+            return Ok(None);
+        }
+        let mut team: Team = serde_json::from_value(data).context("Invalid team data")?;
+        // 部分接口不返回 isLeader，按 leaderId 补齐
+        if let Some(leader) = team.leader_id.clone() {
+            for m in &mut team.members {
+                m.is_leader |= m.id == leader;
+            }
+        }
+        Ok(Some(team))
+    }
+
+    /// `user_id` 为 `User.id`；没有 `leaderId` 时按成员的 `isLeader` 判断。
+    pub fn is_leader(&self, user_id: &str) -> bool {
+        match &self.leader_id {
+            Some(leader) => leader == user_id,
+            None => self.members.iter().any(|m| m.is_leader && m.id == user_id),
+        }
+    }
+
+    /// 当前用户在该活动中的队伍。
+    pub async fn mine(service: &YouthService, item_id: &str) -> Result<Option<Team>> {
+        let data = service
+            .get_result(MY_TEAM_URL, Some(json!({ "itemId": item_id })))
+            .await?;
+        Self::from_result(data)
+    }
+
+    pub async fn by_code(service: &YouthService, code: &str) -> Result<Option<Team>> {
+        let data = service
+            .get_result(BY_CODE_URL, Some(json!({ "code": code.trim() })))
+            .await?;
+        Self::from_result(data)
+    }
+
+    /// 以当前用户为队长建队并报名。`leader_id` 与 `member_ids` 都是用户 ID（`User.id`，
+    /// 可由 `User::find` 查得），不是学号。
+    pub async fn create(
+        service: &YouthService,
+        sc: &SecondClass,
+        leader_id: &str,
+        name: &str,
+        member_ids: &[String],
+    ) -> Result<Option<Team>> {
+        let limits = TeamLimits::require(sc)?;
+        if name.trim().is_empty() {
+            bail!(team_invalid("队名不能为空"));
+        }
+        limits
+            .check_create(leader_id, member_ids)
+            .map_err(team_invalid)?;
+
+        let url = format!("mobile/item/team/create/{}", sc.id);
+        let body = json!({ "teamName": name.trim(), "memberIds": member_ids.join(",") });
+        service.request(&url, "post", None, Some(body)).await?;
+        Self::mine(service, &sc.id).await
+    }
+
+    /// 凭邀请码加入队伍，返回加入后的队伍。
+    pub async fn join(
+        service: &YouthService,
+        sc: &SecondClass,
+        user_id: &str,
+        code: &str,
+    ) -> Result<Option<Team>> {
+        let limits = TeamLimits::require(sc)?;
+        let Some(team) = Self::by_code(service, code).await? else {
+            bail!(team_invalid("邀请码无效"));
+        };
+        if team.item_id.as_deref().is_some_and(|id| id != sc.id) {
+            bail!(team_invalid("邀请码不属于该活动"));
+        }
+        limits.check_join(&team, user_id).map_err(team_invalid)?;
+
+        let body = json!({ "code": code.trim() });
+        service
+            .request("mobile/item/team/join", "post", None, Some(body))
+            .await?;
+        Self::mine(service, &sc.id).await
+    }
+
+    /// 成员退出队伍；队长需使用 `disband`。
+    pub async fn leave(&self, service: &YouthService, user_id: &str) -> Result<()> {
+        if self.is_leader(user_id) {
+            bail!(team_invalid("队长不能退出队伍，请解散队伍"));
+        }
+        let url = format!("mobile/item/team/quit/{}", self.id);
+        service.request(&url, "post", None, None).await?;
+        Ok(())
+    }
+
+    /// 解散队伍，仅队长可操作。
+    pub async fn disband(&self, service: &YouthService, user_id: &str) -> Result<()> {
+        if !self.is_leader(user_id) {
+            bail!(team_invalid("只有队长可以解散队伍"));
+        }
+        let url = format!("mobile/item/team/dissolve/{}", self.id);
+        service.request(&url, "post", None, None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::{cst_serde, CstDateTime, SecondClass, Status};
+use anyhow::{bail, Context, Result};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+// ==================== 候补 (Waitlist) ====================
+//
+// 名额已满（`applyNum >= peopleNum`）的活动加入候补后，后台按自适应间隔轮询 `queryById`，
+// 一旦有人取消报名就自动报名。报名截止（`applyEt`）后停止。
+// 只有接口的业务拒绝会结束候补；名额被抢、网络问题与会话失效都继续候补，稍后重试。
+
+/// 最短轮询间隔（接近截止或名额刚有变动时）
+pub const MIN_INTERVAL_SECS: i64 = 30;
+/// 最长轮询间隔
+pub const MAX_INTERVAL_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum WaitState {
+    Waiting,
+    Applied,
+    Expired,
+    Failed { message: String },
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitEntry {
+    pub activity_id: String,
+    pub activity_name: String,
+    /// 报名截止时间，过后停止候补
+    #[serde(with = "cst_serde")]
+    pub deadline: CstDateTime,
+    pub profile_id: Option<String>,
+    pub auto_cancel: bool,
+    pub state: WaitState,
+    /// 上次轮询时的报名人数，用于判断名额是否有变动
+    pub last_apply_num: Option<i32>,
+    #[serde(with = "cst_serde")]
+    pub next_check_at: CstDateTime,
+    #[serde(with = "cst_serde")]
+    pub created_at: CstDateTime,
+}
+
+/// 报名中且仍有空位（未设人数上限视为有空位）。
+pub fn has_free_seat(sc: &SecondClass) -> bool {
+    sc.status() == Status::Applying
+        && sc
+            .apply_limit
+            .is_none_or(|limit| sc.apply_num.unwrap_or(0) < limit)
+}
+
+/// 轮询间隔：距截止越近越频繁；名额刚有变动时取最短间隔。
+pub fn next_interval(deadline: CstDateTime, now: CstDateTime, seats_changed: bool) -> Duration {
+    let remaining = deadline - now;
+    let secs = if seats_changed || remaining <= Duration::hours(1) {
+        MIN_INTERVAL_SECS
+    } else if remaining <= Duration::days(1) {
+        2 * 60
+    } else {
+        MAX_INTERVAL_SECS
+    };
+    Duration::seconds(secs)
+}
+
+/// 报名失败后是否结束候补（业务拒绝）。名额已满、网络问题与会话失效时继续候补。
+pub fn ends_waiting(err: &AppError) -> bool {
+    !(err.retryable()
+        || matches!(
+            err,
+            AppError::ActivityFull(_) | AppError::SessionExpired(_) | AppError::AuthRequired
+        ))
+}
+
+impl WaitEntry {
+    pub fn new(
+        sc: &SecondClass,
+        profile_id: Option<String>,
+        auto_cancel: bool,
+        now: CstDateTime,
+    ) -> Result<Self> {
+        if sc.applied() {
+            bail!("已报名该活动");
+        }
+        let deadline = sc.apply_time().context("活动没有报名时间")?.end;
+        if deadline <= now {
+            bail!("报名已截止");
+        }
+        Ok(Self {
+            activity_id: sc.id.clone(),
+            activity_name: sc.name.clone(),
+            deadline,
+            profile_id,
+            auto_cancel,
+            state: WaitState::Waiting,
+            last_apply_num: sc.apply_num,
+            next_check_at: now,
+            created_at: now,
+        })
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.state == WaitState::Waiting
+    }
+
+    pub fn is_due(&self, now: CstDateTime) -> bool {
+        self.is_waiting() && self.next_check_at <= now
+    }
+
+    /// 记录一次轮询结果并安排下一次；截止后转为 `Expired`。
+    pub fn observe(&mut self, sc: &SecondClass, now: CstDateTime) {
+        if now >= self.deadline {
+            self.state = WaitState::Expired;
+            return;
+        }
+        let changed = self.last_apply_num.is_some() && self.last_apply_num != sc.apply_num;
+        self.last_apply_num = sc.apply_num;
+        self.next_check_at = (now + next_interval(self.deadline, now, changed)).min(self.deadline);
+    }
+
+    /// 查询失败时推迟下一次检查，状态不变；截止后转为 `Expired`。
+    pub fn defer(&mut self, now: CstDateTime) {
+        if now >= self.deadline {
+            self.state = WaitState::Expired;
+            return;
+        }
+        self.next_check_at = (now + next_interval(self.deadline, now, false)).min(self.deadline);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::rustustc::young::model::{cst_serde, raw_flag, CstDateTime, SecondClass, Status};
+use serde::{Deserialize, Serialize};
+
+// ==================== 变更检测 (Watch) ====================
+//
+// 对已报名活动的 `queryById` 详情做快照，刷新时比较时间（`st`/`et`）、地点（`placeInfo`）、
+// 状态（`itemStatus`）与是否取消，生成结构化的变更事件。
+
+/// 活动详情中需要关注的字段。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub name: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub place: Option<String>,
+    pub status_code: i32,
+    pub cancelled: bool,
+}
+
+impl Snapshot {
+    pub fn of(sc: &SecondClass) -> Self {
+        Self {
+            id: sc.id.clone(),
+            name: sc.name.clone(),
+            start: sc.start_time.clone(),
+            end: sc.end_time.clone(),
+            place: sc.place(),
+            status_code: sc.status_code,
+            cancelled: is_cancelled(sc),
+        }
+    }
+}
+
+/// 活动被删除（`delFlag`）或异常结项视为取消。
+pub fn is_cancelled(sc: &SecondClass) -> bool {
+    raw_flag(sc.raw.get("delFlag")) || sc.status() == Status::AbnormalFinished
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    TimeChanged {
+        old_start: Option<String>,
+        old_end: Option<String>,
+        new_start: Option<String>,
+        new_end: Option<String>,
+    },
+    PlaceChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    StatusChanged {
+        old: i32,
+        new: i32,
+    },
+    Cancelled,
+    /// 报名记录消失（被取消报名或审核未通过）
+    Unregistered,
+}
+
+impl Change {
+    pub fn describe(&self) -> String {
+        let or_none = |s: &Option<String>| s.clone().unwrap_or_else(|| "未定".into());
+        match self {
+            Change::TimeChanged {
+                new_start, new_end, ..
+            } => format!("时间变更为 {} 至 {}", or_none(new_start), or_none(new_end)),
+            Change::PlaceChanged { new, .. } => format!("地点变更为 {}", or_none(new)),
+            Change::StatusChanged { old, new } => format!(
+                "状态由「{}」变为「{}」",
+                Status::from(*old).text(),
+                Status::from(*new).text()
+            ),
+            Change::Cancelled => "活动已取消".into(),
+            Change::Unregistered => "报名记录已不存在".into(),
+        }
+    }
+
+    /// 是否影响活动时间安排（提醒需要重新计算）。
+    pub fn affects_schedule(&self) -> bool {
+        !matches!(
+            self,
+            Change::PlaceChanged { .. } | Change::StatusChanged { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub activity_id: String,
+    pub activity_name: String,
+    #[serde(with = "cst_serde")]
+    pub detected_at: CstDateTime,
+    pub change: Change,
+}
+
+impl ChangeEvent {
+    pub fn message(&self) -> String {
+        format!("「{}」{}", self.activity_name, self.change.describe())
+    }
+}
+
+/// 比较两次快照；已取消的活动不再报告其他字段的变化。
+pub fn diff(old: &Snapshot, new: &Snapshot) -> Vec<Change> {
+    if new.cancelled {
+        return if old.cancelled {
+            Vec::new()
+        } else {
+            vec![Change::Cancelled]
+        };
+    }
+    let mut changes = Vec::new();
+    if old.start != new.start || old.end != new.end {
+        changes.push(Change::TimeChanged {
+            old_start: old.start.clone(),
+            old_end: old.end.clone(),
+            new_start: new.start.clone(),
+            new_end: new.end.clone(),
+        });
+    }
+    if old.place != new.place {
+        changes.push(Change::PlaceChanged {
+            old: old.place.clone(),
+            new: new.place.clone(),
+        });
+    }
+    if old.status_code != new.status_code {
+        changes.push(Change::StatusChanged {
+            old: old.status_code,
+            new: new.status_code,
+        });
+    }
+    changes
+}
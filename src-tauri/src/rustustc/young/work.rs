@@ -0,0 +1,219 @@
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::{raw_flag, CstDateTime, SecondClass, TimePeriod};
+use crate::rustustc::young::service::YouthService;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// ==================== 作品提交 (Work) ====================
+//
+// `canSubmitWork` 的活动需要上传作品或报告，截止时间为 `attaEndTime`。
+// 截止时间在本地检查，避免上传完文件才被服务器拒绝。
+//
+// 未经验证：文件列表 `item/scItemWork/list`、提交 `mobile/item/submitWork/{id}`（请求体
+// `attachment`/`remark`）与 `workStatus` 编码（0 审核中、1 通过、2 驳回）都是按其他二课接口推测的，
+// 尚未用真实账号抓包确认；`sys/common/upload` 为 Jeecg 通用上传接口。
+
+const LIST_URL: &str = "item/scItemWork/list";
+const UPLOAD_URL: &str = "sys/common/upload";
+
+/// 单次最多提交的文件数
+pub const MAX_WORK_FILES: usize = 5;
+/// 单个文件最大字节数
+pub const MAX_WORK_FILE_SIZE: usize = 20 * 1024 * 1024;
+
+/// 作品审核状态（`workStatus`，编码未经验证）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkStatus {
+    NotSubmitted,
+    Reviewing,
+    Approved,
+    Rejected,
+    Unknown,
+}
+
+impl WorkStatus {
+    pub fn from_raw(v: Option<&Value>) -> Self {
+        let code = match v {
+            None | Some(Value::Null) => return WorkStatus::NotSubmitted,
+            Some(Value::Number(n)) => n.as_i64(),
+            Some(Value::String(s)) if s.trim().is_empty() => return WorkStatus::NotSubmitted,
+            Some(Value::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        };
+        match code {
+            Some(0) => WorkStatus::Reviewing,
+            Some(1) => WorkStatus::Approved,
+            Some(2) => WorkStatus::Rejected,
+            _ => WorkStatus::Unknown,
+        }
+    }
+}
+
+/// 提交窗口：是否可提交、截止时间与不可提交的原因。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubmissionWindow {
+    pub open: bool,
+    pub deadline: Option<CstDateTime>,
+    pub reason: Option<String>,
+}
+
+/// 已提交的文件。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkFile {
+    pub id: Option<String>,
+    #[serde(alias = "fileName")]
+    pub name: Option<String>,
+    /// 服务器存储路径
+    #[serde(alias = "filePath", alias = "attachment")]
+    pub path: Option<String>,
+    #[serde(alias = "createTime")]
+    pub create_time_str: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkSubmission {
+    pub window: SubmissionWindow,
+    pub status: WorkStatus,
+    /// `workStatus_dictText`，接口提供时优先展示
+    pub status_text: Option<String>,
+    pub files: Vec<WorkFile>,
+}
+
+pub fn can_submit_work(sc: &SecondClass) -> bool {
+    raw_flag(sc.raw.get("canSubmitWork"))
+}
+
+pub fn deadline(sc: &SecondClass) -> Option<CstDateTime> {
+    sc.raw
+        .get("attaEndTime")
+        .and_then(|v| v.as_str())
+        .and_then(|s| TimePeriod::parse(s).ok())
+}
+
+/// 需要开启作品提交、已报名、未过截止时间，且作品未审核通过。
+pub fn window(sc: &SecondClass, now: CstDateTime) -> SubmissionWindow {
+    let deadline = deadline(sc);
+    let closed = |reason: &str| SubmissionWindow {
+        open: false,
+        deadline,
+        reason: Some(reason.to_string()),
+    };
+    if !can_submit_work(sc) {
+        return closed("该活动无需提交作品");
+    }
+    if sc.boolean_registration == Some(0) {
+        return closed("未报名该活动");
+    }
+    if deadline.is_some_and(|d| now > d) {
+        return closed("已过提交截止时间");
+    }
+    if WorkStatus::from_raw(sc.raw.get("workStatus")) == WorkStatus::Approved {
+        return closed("作品已审核通过");
+    }
+    SubmissionWindow {
+        open: true,
+        deadline,
+        reason: None,
+    }
+}
+
+pub fn validate_files(files: &[(String, Vec<u8>)]) -> Result<()> {
+    if files.is_empty() {
+        bail!("No file selected");
+    }
+    if files.len() > MAX_WORK_FILES {
+        bail!("At most {} files are allowed", MAX_WORK_FILES);
+    }
+    if let Some((name, _)) = files
+        .iter()
+        .find(|(_, bytes)| bytes.len() > MAX_WORK_FILE_SIZE)
+    {
+        bail!("File {} exceeds 20 MB", name);
+    }
+    Ok(())
+}
+
+/// `regPicUrls` 中随活动返回的已提交文件（逗号分隔的存储路径）。
+fn reg_pic_files(sc: &SecondClass) -> Vec<WorkFile> {
+    sc.raw
+        .get("regPicUrls")
+        .and_then(|v| v.as_str())
+        .map(|s| {
+            s.split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(|p| WorkFile {
+                    id: None,
+                    name: p.rsplit('/').next().map(|n| n.to_string()),
+                    path: Some(p.to_string()),
+                    create_time_str: None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 文件列表接口本身不可用（推测的路径不存在或响应格式不符），此时回退到 `regPicUrls`。
+/// 网络问题与会话失效照常返回。
+pub fn list_unavailable(err: &AppError) -> bool {
+    matches!(err, AppError::Youth(_) | AppError::Parse(_))
+}
+
+impl WorkSubmission {
+    /// 提交窗口、审核状态与已提交文件；文件列表接口不可用时回退到 `regPicUrls`。
+    pub async fn load(service: &YouthService, sc: &SecondClass, now: CstDateTime) -> Result<Self> {
+        let files = match service
+            .page_search(LIST_URL, json!({ "itemId": sc.id }), -1, 20)
+            .await
+        {
+            Ok(raw) => raw
+                .into_iter()
+                .filter_map(|v| serde_json::from_value(v).ok())
+                .collect(),
+            Err(e) => match AppError::from(e) {
+                e if list_unavailable(&e) => reg_pic_files(sc),
+                e => return Err(e.into()),
+            },
+        };
+        Ok(Self {
+            window: window(sc, now),
+            status: WorkStatus::from_raw(sc.raw.get("workStatus")),
+            status_text: sc
+                .raw
+                .get("workStatus_dictText")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            files,
+        })
+    }
+
+    /// 检查窗口与文件后逐个上传，再提交存储路径。
+    pub async fn submit(
+        service: &YouthService,
+        sc: &SecondClass,
+        files: Vec<(String, Vec<u8>)>,
+        remark: Option<&str>,
+        now: CstDateTime,
+    ) -> Result<Vec<String>> {
+        let w = window(sc, now);
+        if !w.open {
+            bail!("{}", w.reason.unwrap_or_default());
+        }
+        validate_files(&files)?;
+
+        let mut paths = Vec::with_capacity(files.len());
+        for (name, bytes) in files {
+            paths.push(service.upload(UPLOAD_URL, &name, bytes).await?);
+        }
+        // 未经验证：提交接口路径与字段名为推测，见模块说明
+        let url = format!("mobile/item/submitWork/{}", sc.id);
+        let body = json!({
+            "attachment": paths.join(","),
+            "remark": remark.unwrap_or_default().trim(),
+        });
+        service.request(&url, "post", None, Some(body)).await?;
+        Ok(paths)
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use rand::{thread_rng, RngCore};
+use serde_json::json;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::rustustc::young::model::now;
+use crate::rustustc::young::saved_search::SavedSearch;
+use crate::rustustc::young::{SecondClass, YouthService};
+use crate::state::AppState;
+
+const SEARCHES_STORE: &str = "saved_searches.json";
+/// 后台执行间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// 有新匹配时发送，载荷为 `{search_id, name, activity_ids}`。
+pub const NEW_MATCHES_EVENT: &str = "search://new-matches";
+
+/// 串行化对 `saved_searches.json` 的读改写（命令与后台执行会同时写入）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn list(app: &AppHandle) -> Result<Vec<SavedSearch>> {
+    let store = app
+        .store(SEARCHES_STORE)
+        .context("Failed to access store")?;
+    Ok(store
+        .get("searches")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save_all(app: &AppHandle, searches: &[SavedSearch]) -> Result<()> {
+    let store = app
+        .store(SEARCHES_STORE)
+        .context("Failed to access store")?;
+    store.set("searches", json!(searches));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 在写锁内读取搜索列表、修改并写回。
+fn modify<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<SavedSearch>) -> T) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut searches = list(app)?;
+    let out = f(&mut searches);
+    save_all(app, &searches)?;
+    Ok(out)
+}
+
+fn new_id() -> String {
+    let mut bytes = [0u8; 8];
+    thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// 新建（`id` 为空）或更新。查询条件变化时清空已读记录，下次执行重新建立基线。
+pub fn save(app: &AppHandle, mut search: SavedSearch) -> Result<SavedSearch> {
+    if search.id.is_empty() {
+        search.id = new_id();
+    }
+    modify(app, |searches| {
+        match searches.iter_mut().find(|s| s.id == search.id) {
+            Some(existing) => {
+                if existing.query == search.query {
+                    search.seen = std::mem::take(&mut existing.seen);
+                    search.unseen = std::mem::take(&mut existing.unseen);
+                    search.last_run_at = existing.last_run_at;
+                } else {
+                    search.seen.clear();
+                    search.unseen.clear();
+                    search.last_run_at = None;
+                }
+                *existing = search.clone();
+            }
+            None => searches.push(search.clone()),
+        }
+    })?;
+    Ok(search)
+}
+
+pub fn delete(app: &AppHandle, id: &str) -> Result<()> {
+    modify(app, |searches| searches.retain(|s| s.id != id))
+}
+
+/// 将新匹配标为已读；`id` 为空时处理全部搜索。
+pub fn mark_seen(app: &AppHandle, id: Option<&str>) -> Result<()> {
+    modify(app, |searches| {
+        for s in searches
+            .iter_mut()
+            .filter(|s| id.is_none_or(|id| s.id == id))
+        {
+            s.mark_all_seen();
+        }
+    })
+}
+
+/// 执行一个搜索并记录结果，返回匹配的活动与本次新出现的 ID。
+pub async fn run(
+    app: &AppHandle,
+    service: &YouthService,
+    id: &str,
+) -> Result<(Vec<SecondClass>, Vec<String>)> {
+    let search = list(app)?
+        .into_iter()
+        .find(|s| s.id == id)
+        .with_context(|| format!("Saved search {} not found", id))?;
+    // 与 `search_activities` 相同：状态条件决定查报名中列表、已结束列表或两者
+    let results = SecondClass::find_by_status(service, search.filter.clone()).await?;
+    let ids: Vec<String> = results.iter().map(|sc| sc.id.clone()).collect();
+
+    // 执行期间可能被编辑或删除，基于最新的存储写回
+    let recorded = modify(app, |searches| {
+        let search = searches.iter_mut().find(|s| s.id == id)?;
+        let new = search.record(&ids, now());
+        Some((new, search.name.clone(), search.notify))
+    })?;
+    let Some((new, name, notify)) = recorded else {
+        return Ok((results, Vec::new()));
+    };
+
+    if !new.is_empty() {
+        let _ = app.emit(
+            NEW_MATCHES_EVENT,
+            json!({ "search_id": id, "name": name, "activity_ids": new }),
+        );
+        if notify {
+            let first = results
+                .iter()
+                .find(|sc| sc.id == new[0])
+                .map(|sc| sc.name.clone())
+                .unwrap_or_default();
+            let body = if new.len() == 1 {
+                format!("「{}」有新活动：{}", name, first)
+            } else {
+                format!("「{}」有 {} 个新活动，如：{}", name, new.len(), first)
+            };
+            let _ = app
+                .notification()
+                .builder()
+                .title("新活动")
+                .body(body)
+                .show();
+        }
+    }
+    Ok((results, new))
+}
+
+/// 登录期间定时执行全部搜索；未登录时跳过。
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let service = app.state::<AppState>().session.current_service();
+            let (Some(service), Ok(searches)) = (service, list(&app)) else {
+                continue;
+            };
+            for search in searches {
+                let _ = run(&app, &service, &search.id).await;
+            }
+        }
+    });
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app
+        .store(SEARCHES_STORE)
+        .context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use std::sync::{Arc, Mutex as StdMutex, Weak};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_log::log;
+use tokio::sync::Mutex;
+
+use crate::logout;
+use crate::rustustc::cas::client::CASClient;
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::{now, CstDateTime, User};
+pub use crate::rustustc::young::session::SessionState;
+use crate::rustustc::young::YouthService;
+use crate::state::AppState;
+
+/// 会话状态变化时发送，载荷为 `SessionState`。
+pub const SESSION_EVENT: &str = "session://changed";
+
+/// 一次登录得到的 CAS 客户端、二课服务与用户信息。
+#[derive(Clone)]
+pub struct Session {
+    pub username: String,
+    pub cas: Arc<CASClient>,
+    pub youth: Arc<YouthService>,
+    pub user: User,
+    /// 当前二课 token 的获取时间
+    pub token_since: CstDateTime,
+}
+
+/// 接口返回 token 失效时把会话标记为 `Expired`；该服务已被刷新替换时忽略。
+fn watch_expiry(app: &AppHandle, youth: &Arc<YouthService>) {
+    let app = app.clone();
+    let service = Arc::downgrade(youth);
+    youth.on_session_expired(move |reason| {
+        app.state::<AppState>()
+            .session
+            .expire_service(&app, &service, reason);
+    });
+}
+
+impl Session {
+    /// CAS 登录 → 二课登录 → 获取用户信息（验证 token）。
+    async fn establish(app: &AppHandle, username: &str, password: &str) -> anyhow::Result<Self> {
+        let cas = CASClient::new();
+        cas.login_by_pwd(Some(username), Some(password)).await?;
+        let youth = Arc::new(YouthService::new(&cas).await?);
+        watch_expiry(app, &youth);
+        let user = User::get_current(&youth).await?;
+        Ok(Self {
+            username: username.to_string(),
+            cas: Arc::new(cas),
+            youth,
+            user,
+            token_since: now(),
+        })
+    }
+}
+
+struct Current {
+    state: SessionState,
+    session: Option<Session>,
+}
+
+/// 登录状态机。登录、刷新、登出依次执行，读取状态不等待进行中的转换。
+pub struct SessionManager {
+    transition: Mutex<()>,
+    current: StdMutex<Current>,
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        Self {
+            transition: Mutex::new(()),
+            current: StdMutex::new(Current {
+                state: SessionState::LoggedOut,
+                session: None,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Current> {
+        self.current.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.lock().state.clone()
+    }
+
+    /// 可用的会话（`Active` 或 `Refreshing`）。
+    pub fn session(&self) -> Option<Session> {
+        let current = self.lock();
+        if current.state.is_usable() {
+            current.session.clone()
+        } else {
+            None
+        }
+    }
+
+    /// 后台任务用：未登录或会话失效时为 `None`。
+    pub fn current_service(&self) -> Option<Arc<YouthService>> {
+        self.session().map(|s| s.youth)
+    }
+
+    /// 命令用：区分未登录与会话失效。
+    pub fn service(&self) -> Result<Arc<YouthService>, AppError> {
+        match self.session() {
+            Some(s) => Ok(s.youth),
+            None => Err(self.state().unavailable_error()),
+        }
+    }
+
+    fn set(&self, app: &AppHandle, state: SessionState, session: Option<Option<Session>>) {
+        let changed = {
+            let mut current = self.lock();
+            if let Some(session) = session {
+                current.session = session;
+            }
+            let changed = current.state != state;
+            current.state = state.clone();
+            changed
+        };
+        if changed {
+            let _ = app.emit(SESSION_EVENT, &state);
+        }
+    }
+
+    /// 登录。并发的重复登录会等待前一次完成；同一账号已登录且 token 验证有效时直接复用，
+    /// 否则重新登录。
+    pub async fn login(
+        &self,
+        app: &AppHandle,
+        username: &str,
+        password: &str,
+    ) -> Result<User, AppError> {
+        let _transition = self.transition.lock().await;
+        if let Some(s) = self.session().filter(|_| self.state().can_reuse(username)) {
+            if let Ok(user) = User::get_current(&s.youth).await {
+                return Ok(user);
+            }
+        }
+
+        self.set(
+            app,
+            SessionState::LoggingIn {
+                username: username.to_string(),
+            },
+            None,
+        );
+        match Session::establish(app, username, password).await {
+            Ok(session) => {
+                // 换了账号时先清除上一账号的本地数据，再让后台任务使用新会话
+                match logout::switch_account(app, username).await {
+                    Ok(Some(report)) => {
+                        for step in report.failed() {
+                            log::warn!("Failed to clear {}: {:?}", step.step, step.error);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::warn!("Failed to check the data owner: {:#}", e),
+                }
+                let user = session.user.clone();
+                self.set(
+                    app,
+                    SessionState::Active {
+                        username: username.to_string(),
+                    },
+                    Some(Some(session)),
+                );
+                Ok(user)
+            }
+            Err(e) => {
+                let err = AppError::from(e);
+                self.set(
+                    app,
+                    SessionState::Error {
+                        message: err.message().to_string(),
+                    },
+                    Some(None),
+                );
+                Err(err)
+            }
+        }
+    }
+
+    /// 用当前 CAS Cookie 重新获取二课 token。CAS 会话失效时转为 `Expired`；
+    /// 网络错误保留原会话。
+    pub async fn refresh(&self, app: &AppHandle) -> Result<User, AppError> {
+        let _transition = self.transition.lock().await;
+        let Some(old) = self.lock().session.clone() else {
+            return Err(AppError::AuthRequired);
+        };
+        let previous = self.state();
+        let username = old.username.clone();
+        self.set(
+            app,
+            SessionState::Refreshing {
+                username: username.clone(),
+            },
+            None,
+        );
+
+        let result = async {
+            if !old.cas.is_login().await {
+                return Err(AppError::SessionExpired(
+                    "CAS Session expired. Please login again.".into(),
+                ));
+            }
+            let youth = Arc::new(YouthService::new(&old.cas).await?);
+            watch_expiry(app, &youth);
+            let user = User::get_current(&youth).await?;
+            Ok((youth, user))
+        }
+        .await;
+
+        let next = SessionState::after_refresh(previous, &username, result.as_ref().map(|_| ()));
+        match result {
+            Ok((youth, user)) => {
+                let session = Session {
+                    youth,
+                    user: user.clone(),
+                    token_since: now(),
+                    ..old
+                };
+                self.set(app, next, Some(Some(session)));
+                Ok(user)
+            }
+            Err(err) => {
+                self.set(app, next, None);
+                Err(err)
+            }
+        }
+    }
+
+    /// 标记会话失效（如接口返回 token 失效），保留 CAS 客户端以便刷新。
+    pub fn mark_expired(&self, app: &AppHandle, reason: &str) {
+        if let Some(expired) = self.state().expire(reason) {
+            self.set(app, expired, None);
+        }
+    }
+
+    /// 某个二课服务的请求返回 token 失效；仅当它仍是当前会话的服务时标记失效。
+    fn expire_service(&self, app: &AppHandle, service: &Weak<YouthService>, reason: &str) {
+        let current = self
+            .lock()
+            .session
+            .as_ref()
+            .is_some_and(|s| Arc::as_ptr(&s.youth) == service.as_ptr());
+        if current {
+            self.mark_expired(app, reason);
+        }
+    }
+
+    /// 登出，返回被清除的会话。
+    pub async fn logout(&self, app: &AppHandle) -> Option<Session> {
+        let _transition = self.transition.lock().await;
+        let session = self.lock().session.take();
+        self.set(app, SessionState::LoggedOut, Some(None));
+        session
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const SETTINGS_STORE: &str = "settings.json";
+
+/// 读取一项设置；不存在或格式不符时返回默认值。
+pub fn load<T: DeserializeOwned + Default>(app: &AppHandle, key: &str) -> Result<T> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .context("Failed to access store")?;
+    Ok(store
+        .get(key)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+pub fn save<T: Serialize>(app: &AppHandle, key: &str, value: &T) -> Result<()> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .context("Failed to access store")?;
+    store.set(key, serde_json::to_value(value)?);
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
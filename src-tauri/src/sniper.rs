@@ -0,0 +1,270 @@
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::auth;
+use crate::profile;
+use crate::reminders;
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::{now, CstDateTime, SignInfo};
+use crate::rustustc::young::rate_limit;
+use crate::rustustc::young::sniper::{should_retry, JobState, SniperJob, RETRY_DELAY_MS};
+use crate::rustustc::young::{SecondClass, YouthService};
+use crate::state::AppState;
+
+const SNIPER_STORE: &str = "sniper.json";
+/// 长时间等待时分段睡眠，避免系统休眠后计时不准
+const MAX_SLEEP: Duration = Duration::from_secs(30);
+
+/// 任务状态变化时发送，载荷为 `SniperJob`。
+pub const SNIPER_EVENT: &str = "sniper://updated";
+
+/// 串行化对 `sniper.json` 的读改写（命令与后台任务会同时写入）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 在写锁内读取任务列表、修改并写回。
+fn modify<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<SniperJob>) -> T) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut jobs = list(app)?;
+    let out = f(&mut jobs);
+    save_all(app, &jobs)?;
+    Ok(out)
+}
+
+fn upsert(jobs: &mut Vec<SniperJob>, job: &SniperJob) {
+    match jobs.iter_mut().find(|j| j.activity_id == job.activity_id) {
+        Some(j) => *j = job.clone(),
+        None => jobs.push(job.clone()),
+    }
+}
+
+pub fn list(app: &AppHandle) -> Result<Vec<SniperJob>> {
+    let store = app.store(SNIPER_STORE).context("Failed to access store")?;
+    Ok(store
+        .get("jobs")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save_all(app: &AppHandle, jobs: &[SniperJob]) -> Result<()> {
+    let store = app.store(SNIPER_STORE).context("Failed to access store")?;
+    store.set("jobs", json!(jobs));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 写回单个任务并通知前端。
+fn update(app: &AppHandle, job: &SniperJob) -> Result<()> {
+    modify(app, |jobs| upsert(jobs, job))?;
+    let _ = app.emit(SNIPER_EVENT, job);
+    Ok(())
+}
+
+/// 为尚未开放报名的活动创建定时任务；同一活动只能有一个进行中的任务。
+pub async fn schedule(
+    app: &AppHandle,
+    service: &YouthService,
+    activity_id: &str,
+    profile_id: Option<String>,
+    auto_cancel: bool,
+) -> Result<SniperJob> {
+    if list(app)?
+        .iter()
+        .any(|j| j.activity_id == activity_id && j.is_active())
+    {
+        bail!(AppError::rule("SCHEDULE_INVALID", "该活动已有定时报名任务"));
+    }
+    let sc = SecondClass::get_by_id(service, activity_id).await?;
+    // 只有本地校验失败算业务错误，网络与接口错误原样返回
+    let job = SniperJob::new(&sc, profile_id, auto_cancel, now())
+        .map_err(|e| AppError::rule("SCHEDULE_INVALID", e))?;
+    // 查询期间可能已建立了同一活动的任务，写入时再检查一次
+    let added = modify(app, |jobs| {
+        let duplicate = jobs
+            .iter()
+            .any(|j| j.activity_id == job.activity_id && j.is_active());
+        if !duplicate {
+            upsert(jobs, &job);
+        }
+        !duplicate
+    })?;
+    if !added {
+        bail!(AppError::rule("SCHEDULE_INVALID", "该活动已有定时报名任务"));
+    }
+    let _ = app.emit(SNIPER_EVENT, &job);
+    start(app, job.clone()).await;
+    Ok(job)
+}
+
+/// 取消进行中的任务；任务不存在或已结束时返回 `false`。
+pub async fn cancel(app: &AppHandle, activity_id: &str) -> Result<bool> {
+    if let Some(handle) = app
+        .state::<AppState>()
+        .sniper_tasks
+        .lock()
+        .await
+        .remove(activity_id)
+    {
+        handle.abort();
+    }
+    let job = modify(app, |jobs| {
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.activity_id == activity_id && j.is_active())?;
+        job.state = JobState::Cancelled;
+        Some(job.clone())
+    })?;
+    match job {
+        Some(job) => {
+            let _ = app.emit(SNIPER_EVENT, &job);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 终止所有进行中的任务并清空任务列表（登出时调用）。
+pub async fn clear(app: &AppHandle) -> Result<()> {
+    for (_, handle) in app.state::<AppState>().sniper_tasks.lock().await.drain() {
+        handle.abort();
+    }
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app.store(SNIPER_STORE).context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 应用启动时恢复未完成的任务；错过开放时间太久的标记为失败。
+pub async fn restore(app: &AppHandle) -> Result<()> {
+    let now = now();
+    for mut job in list(app)?.into_iter().filter(|j| j.is_active()) {
+        if job.missed(now) {
+            job.state = JobState::Failed {
+                message: "应用未运行，错过了报名开放时间".into(),
+            };
+            update(app, &job)?;
+        } else {
+            start(app, job).await;
+        }
+    }
+    Ok(())
+}
+
+/// 持有任务表的锁启动任务并登记句柄；任务结束时移除句柄也要取这把锁，
+/// 立即结束的任务不会留下过期的句柄。
+async fn start(app: &AppHandle, job: SniperJob) {
+    let state = app.state::<AppState>();
+    let mut tasks = state.sniper_tasks.lock().await;
+    let id = job.activity_id.clone();
+    let handle = tauri::async_runtime::spawn(run(app.clone(), job));
+    tasks.insert(id, handle);
+}
+
+async fn sleep_until(target: CstDateTime) {
+    loop {
+        let remaining = match (target - now()).to_std() {
+            Ok(d) if !d.is_zero() => d,
+            _ => return,
+        };
+        tokio::time::sleep(remaining.min(MAX_SLEEP)).await;
+    }
+}
+
+fn current_service(app: &AppHandle) -> Option<Arc<YouthService>> {
+    app.state::<AppState>().session.current_service()
+}
+
+/// 校验登录（必要时用保存的凭据重新登录），刷新活动详情并预取报名信息。
+async fn prewarm(
+    app: &AppHandle,
+    job: &SniperJob,
+) -> Result<(Arc<YouthService>, SecondClass, Option<SignInfo>)> {
+    let (logged_in, ..) = auth::try_auto_login(app).await?;
+    let service = match current_service(app) {
+        Some(s) if logged_in => s,
+        _ => bail!("登录已失效，无法报名"),
+    };
+    let sc = SecondClass::get_by_id(&service, &job.activity_id).await?;
+    let sign_info = if sc.need_sign_info() {
+        match profile::resolve_sign_info(app, &job.activity_id, job.profile_id.as_deref(), None)? {
+            Some(info) => Some(info),
+            None => Some(SignInfo::get_self(&service).await?),
+        }
+    } else {
+        None
+    };
+    Ok((service, sc, sign_info))
+}
+
+async fn run(app: AppHandle, mut job: SniperJob) {
+    let skew = match current_service(&app) {
+        Some(s) => s.clock_skew(),
+        None => None,
+    };
+    sleep_until(job.prewarm_at(skew)).await;
+
+    job.state = JobState::Warming;
+    let _ = update(&app, &job);
+    // 预热与报名不排在后台轮询之后
+    job.state = match rate_limit::priority(prewarm(&app, &job)).await {
+        Ok((service, sc, sign_info)) => {
+            // 预热请求刚刷新过时钟偏差
+            sleep_until(job.fire_at(service.clock_skew())).await;
+            job.state = JobState::Applying;
+            let _ = update(&app, &job);
+            loop {
+                job.attempts += 1;
+                let apply = sc.apply(&service, true, job.auto_cancel, sign_info.clone(), |id| {
+                    profile::resolve_sign_info(&app, id, None, None)
+                        .ok()
+                        .flatten()
+                });
+                match rate_limit::priority(apply).await {
+                    Ok(true) => {
+                        reminders::resync_in_background(&app, service.clone());
+                        break JobState::Succeeded;
+                    }
+                    Ok(false) => {
+                        break JobState::Failed {
+                            message: "报名未成功".into(),
+                        }
+                    }
+                    Err(e) if should_retry(&e.to_string(), job.attempts) => {
+                        tokio::time::sleep(Duration::from_millis(RETRY_DELAY_MS)).await;
+                    }
+                    Err(e) => {
+                        break JobState::Failed {
+                            message: e.to_string(),
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => JobState::Failed {
+            message: e.to_string(),
+        },
+    };
+    let _ = update(&app, &job);
+
+    let body = match &job.state {
+        JobState::Succeeded => format!("「{}」报名成功", job.activity_name),
+        JobState::Failed { message } => format!("「{}」报名失败：{}", job.activity_name, message),
+        _ => String::new(),
+    };
+    let _ = app
+        .notification()
+        .builder()
+        .title("定时报名")
+        .body(body)
+        .show();
+    app.state::<AppState>()
+        .sniper_tasks
+        .lock()
+        .await
+        .remove(&job.activity_id);
+}
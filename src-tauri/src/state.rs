@@ -1,18 +1,31 @@
-use crate::rustustc::cas::client::CASClient;
-use crate::rustustc::young::YouthService;
+use crate::rustustc::young::TagIndex;
+use crate::session::SessionManager;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
 use tokio::sync::Mutex;
 
 pub struct AppState {
-    pub cas_client: Mutex<Option<Arc<CASClient>>>,
-    pub youth_service: Mutex<Option<Arc<YouthService>>>,
+    /// 登录状态，命令与后台任务都从这里取 `YouthService`
+    pub session: SessionManager,
+    /// 模块/部门/标签字典，首次使用时加载
+    pub tag_index: Mutex<Option<Arc<TagIndex>>>,
+    /// 进行中的定时报名任务，键为活动 ID
+    pub sniper_tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AppState {
     pub fn new() -> Self {
         Self {
-            cas_client: Mutex::new(None),
-            youth_service: Mutex::new(None),
+            session: SessionManager::new(),
+            tag_index: Mutex::new(None),
+            sniper_tasks: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -0,0 +1,220 @@
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::profile;
+use crate::reminders;
+use crate::rustustc::error::AppError;
+use crate::rustustc::young::model::{now, SignInfo};
+use crate::rustustc::young::waitlist::{ends_waiting, has_free_seat, WaitEntry, WaitState};
+use crate::rustustc::young::{SecondClass, YouthService};
+use crate::state::AppState;
+
+const WAITLIST_STORE: &str = "waitlist.json";
+/// 后台检查是否有到期候补的间隔；实际轮询间隔由 `next_interval` 决定
+const TICK: Duration = Duration::from_secs(15);
+
+/// 候补状态变化时发送，载荷为 `WaitEntry`。
+pub const WAITLIST_EVENT: &str = "waitlist://updated";
+
+/// 串行化对 `waitlist.json` 的读改写（命令与后台轮询会同时写入）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn list(app: &AppHandle) -> Result<Vec<WaitEntry>> {
+    let store = app
+        .store(WAITLIST_STORE)
+        .context("Failed to access store")?;
+    Ok(store
+        .get("entries")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save_all(app: &AppHandle, entries: &[WaitEntry]) -> Result<()> {
+    let store = app
+        .store(WAITLIST_STORE)
+        .context("Failed to access store")?;
+    store.set("entries", json!(entries));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 在写锁内读取候补列表、修改并写回。
+fn modify<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<WaitEntry>) -> T) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = list(app)?;
+    let out = f(&mut entries);
+    save_all(app, &entries)?;
+    Ok(out)
+}
+
+fn upsert(entries: &mut Vec<WaitEntry>, entry: &WaitEntry) {
+    match entries
+        .iter_mut()
+        .find(|e| e.activity_id == entry.activity_id)
+    {
+        Some(e) => *e = entry.clone(),
+        None => entries.push(entry.clone()),
+    }
+}
+
+pub async fn join(
+    app: &AppHandle,
+    service: &YouthService,
+    activity_id: &str,
+    profile_id: Option<String>,
+    auto_cancel: bool,
+) -> Result<WaitEntry> {
+    if list(app)?
+        .iter()
+        .any(|e| e.activity_id == activity_id && e.is_waiting())
+    {
+        bail!(AppError::rule("WAITLIST_INVALID", "已在候补中"));
+    }
+    let sc = SecondClass::get_by_id(service, activity_id).await?;
+    // 只有本地校验失败算业务错误，网络与接口错误原样返回
+    let entry = WaitEntry::new(&sc, profile_id, auto_cancel, now())
+        .map_err(|e| AppError::rule("WAITLIST_INVALID", e))?;
+    // 查询期间可能已加入了同一活动的候补，写入时再检查一次
+    let added = modify(app, |entries| {
+        let duplicate = entries
+            .iter()
+            .any(|e| e.activity_id == entry.activity_id && e.is_waiting());
+        if !duplicate {
+            upsert(entries, &entry);
+        }
+        !duplicate
+    })?;
+    if !added {
+        bail!(AppError::rule("WAITLIST_INVALID", "已在候补中"));
+    }
+    Ok(entry)
+}
+
+/// 退出候补；不在候补中时返回 `false`。
+pub fn leave(app: &AppHandle, activity_id: &str) -> Result<bool> {
+    modify(app, |entries| {
+        match entries
+            .iter_mut()
+            .find(|e| e.activity_id == activity_id && e.is_waiting())
+        {
+            Some(e) => {
+                e.state = WaitState::Cancelled;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// 报名信息：优先使用保存的 profile 与备注，没有时使用账号自身信息（与抢课一致）。
+async fn sign_info(
+    app: &AppHandle,
+    service: &YouthService,
+    entry: &WaitEntry,
+    sc: &SecondClass,
+) -> Result<Option<SignInfo>> {
+    if !sc.need_sign_info() {
+        return Ok(None);
+    }
+    match profile::resolve_sign_info(app, &sc.id, entry.profile_id.as_deref(), None)? {
+        Some(info) => Ok(Some(info)),
+        None => Ok(Some(SignInfo::get_self(service).await?)),
+    }
+}
+
+/// 轮询一个候补：有空位时立即报名，名额被抢走则继续候补。
+async fn check(app: &AppHandle, service: &Arc<YouthService>, mut entry: WaitEntry) -> Result<()> {
+    let now = now();
+    match SecondClass::get_by_id(service, &entry.activity_id).await {
+        Ok(sc) if sc.applied() => entry.state = WaitState::Applied,
+        Ok(sc) if now < entry.deadline && has_free_seat(&sc) => {
+            let result = match sign_info(app, service, &entry, &sc).await {
+                Ok(info) => {
+                    sc.apply(service, true, entry.auto_cancel, info, |id| {
+                        profile::resolve_sign_info(app, id, None, None)
+                            .ok()
+                            .flatten()
+                    })
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            match result.map_err(AppError::from) {
+                Ok(true) => {
+                    entry.state = WaitState::Applied;
+                    reminders::resync_in_background(app, service.clone());
+                }
+                Err(e) if ends_waiting(&e) => {
+                    entry.state = WaitState::Failed {
+                        message: e.message().to_string(),
+                    }
+                }
+                // 名额被他人抢先、网络问题或会话失效，继续候补
+                _ => entry.observe(&sc, now),
+            }
+        }
+        Ok(sc) => entry.observe(&sc, now),
+        // 查询失败时推迟下一次检查，避免每次 TICK 都重试
+        Err(_) => entry.defer(now),
+    }
+
+    // 轮询中途可能已被用户取消，检查与写回在同一把锁内
+    let cancelled = modify(app, |entries| {
+        let cancelled = entries
+            .iter()
+            .any(|e| e.activity_id == entry.activity_id && e.state == WaitState::Cancelled);
+        if !cancelled {
+            upsert(entries, &entry);
+        }
+        cancelled
+    })?;
+    if cancelled {
+        return Ok(());
+    }
+    if !entry.is_waiting() {
+        let _ = app.emit(WAITLIST_EVENT, &entry);
+        let body = match &entry.state {
+            WaitState::Applied => format!("「{}」候补成功，已自动报名", entry.activity_name),
+            WaitState::Expired => format!("「{}」报名已截止，候补结束", entry.activity_name),
+            WaitState::Failed { message } => {
+                format!("「{}」候补报名失败：{}", entry.activity_name, message)
+            }
+            _ => return Ok(()),
+        };
+        let _ = app.notification().builder().title("候补").body(body).show();
+    }
+    Ok(())
+}
+
+/// 后台逐个检查到期的候补；请求经全局限流，未登录时跳过。
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            let service = app.state::<AppState>().session.current_service();
+            let (Some(service), Ok(entries)) = (service, list(&app)) else {
+                continue;
+            };
+            let now = now();
+            for entry in entries.into_iter().filter(|e| e.is_due(now)) {
+                let _ = check(&app, &service, entry).await;
+            }
+        }
+    });
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app
+        .store(WAITLIST_STORE)
+        .context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::rustustc::young::model::{now, CstDateTime};
+use crate::rustustc::young::watch::{self, Change, ChangeEvent, Snapshot};
+use crate::rustustc::young::{SecondClass, YouthService};
+use crate::state::AppState;
+
+const WATCHER_STORE: &str = "watcher.json";
+/// 每个活动最多保留的变更记录数
+const MAX_LOG_PER_ACTIVITY: usize = 50;
+/// 后台刷新间隔
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// 前端监听的事件名，载荷为 `ChangeEvent`。
+pub const CHANGE_EVENT: &str = "activity://changed";
+
+/// 同一时间只进行一次刷新（后台定时刷新与前端手动刷新可能重叠，重叠时会重复通知同一变更）
+static REFRESHING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+/// 串行化对 `watcher.json` 的读改写（刷新写回与登出时的清空）
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchState {
+    snapshots: HashMap<String, Snapshot>,
+    log: HashMap<String, Vec<ChangeEvent>>,
+}
+
+fn load(app: &AppHandle) -> Result<WatchState> {
+    let store = app.store(WATCHER_STORE).context("Failed to access store")?;
+    Ok(WatchState {
+        snapshots: store
+            .get("snapshots")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+        log: store
+            .get("log")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+    })
+}
+
+fn save(app: &AppHandle, state: &WatchState) -> Result<()> {
+    let store = app.store(WATCHER_STORE).context("Failed to access store")?;
+    store.set("snapshots", json!(state.snapshots));
+    store.set("log", json!(state.log));
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
+
+/// 变更记录，最近的在前；`activity_id` 为空时返回全部活动的记录。
+pub fn changes(app: &AppHandle, activity_id: Option<&str>) -> Result<Vec<ChangeEvent>> {
+    let mut log = load(app)?.log;
+    let mut events: Vec<ChangeEvent> = match activity_id {
+        Some(id) => log.remove(id).unwrap_or_default(),
+        None => log.into_values().flatten().collect(),
+    };
+    events.sort_by_key(|e| std::cmp::Reverse(e.detected_at));
+    Ok(events)
+}
+
+impl WatchState {
+    /// 用刷新得到的快照更新状态，返回新检测到的变更。
+    ///
+    /// `fetched` 为仍报名的活动（`None` 表示详情查询失败，沿用旧快照）；`vanished` 为从列表中
+    /// 消失的活动是否已被取消（`None` 表示查询失败）。
+    fn apply(
+        &mut self,
+        fetched: &[(String, Option<Snapshot>)],
+        participated: &HashSet<String>,
+        vanished: &HashMap<String, Option<bool>>,
+        now: CstDateTime,
+    ) -> Vec<ChangeEvent> {
+        let mut events = Vec::new();
+        let mut push = |sc: &Snapshot, change: Change| {
+            events.push(ChangeEvent {
+                activity_id: sc.id.clone(),
+                activity_name: sc.name.clone(),
+                detected_at: now,
+                change,
+            })
+        };
+
+        let mut current = HashMap::new();
+        for (id, snapshot) in fetched {
+            let Some(snapshot) = snapshot.clone().or_else(|| self.snapshots.get(id).cloned())
+            else {
+                continue;
+            };
+            if let Some(old) = self.snapshots.get(id) {
+                for change in watch::diff(old, &snapshot) {
+                    push(&snapshot, change);
+                }
+            }
+            current.insert(id.clone(), snapshot);
+        }
+
+        for (id, old) in &self.snapshots {
+            if participated.contains(id) {
+                // 仍在列表中说明只是活动已结束，不再跟踪
+                continue;
+            }
+            match vanished.get(id) {
+                Some(Some(cancelled)) => {
+                    if !old.cancelled {
+                        let change = if *cancelled {
+                            Change::Cancelled
+                        } else {
+                            Change::Unregistered
+                        };
+                        push(old, change);
+                    }
+                }
+                // 查询失败时保留旧快照，下次刷新再判断，避免漏报取消
+                _ => {
+                    current.insert(id.clone(), old.clone());
+                }
+            }
+        }
+
+        self.snapshots = current;
+        for event in &events {
+            let log = self.log.entry(event.activity_id.clone()).or_default();
+            log.insert(0, event.clone());
+            log.truncate(MAX_LOG_PER_ACTIVITY);
+        }
+        events
+    }
+}
+
+fn current_account(app: &AppHandle) -> Option<String> {
+    app.state::<AppState>()
+        .session
+        .session()
+        .map(|s| s.username)
+}
+
+/// 在写锁内重新读取状态、修改并写回。`account` 已不是当前登录的账号（刷新期间登出或换了账号）
+/// 时不写回，返回 `None`，避免旧会话的结果在 `clear` 之后重新写入。
+fn modify<T>(
+    app: &AppHandle,
+    account: Option<&str>,
+    f: impl FnOnce(&mut WatchState) -> T,
+) -> Result<Option<T>> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if account.is_none() || current_account(app).as_deref() != account {
+        return Ok(None);
+    }
+    let mut state = load(app)?;
+    let out = f(&mut state);
+    save(app, &state)?;
+    Ok(Some(out))
+}
+
+/// 刷新已报名活动的快照，返回新检测到的变更。
+///
+/// 首次出现的活动只记录快照；从报名列表中消失且未结束的活动再查一次详情，
+/// 区分活动取消与报名被取消。同一时间只进行一次刷新；网络请求完成后在写锁内
+/// 基于最新存储比较并写回。
+pub async fn refresh(app: &AppHandle, service: &YouthService) -> Result<Vec<ChangeEvent>> {
+    let _refreshing = REFRESHING.lock().await;
+    let account = current_account(app);
+    let now = now();
+    let participated = SecondClass::get_participated(service).await?;
+    let participated_ids: HashSet<String> = participated.iter().map(|sc| sc.id.clone()).collect();
+
+    // 列表中的字段不全，以 queryById 详情为准；单个失败时沿用旧快照
+    let mut fetched = Vec::new();
+    let mut details = Vec::new();
+    for sc in participated
+        .iter()
+        .filter(|sc| crate::is_still_registered(sc, now))
+    {
+        match SecondClass::get_by_id(service, &sc.id).await {
+            Ok(detail) => {
+                fetched.push((sc.id.clone(), Some(Snapshot::of(&detail))));
+                details.push(detail);
+            }
+            Err(_) => {
+                fetched.push((sc.id.clone(), None));
+                details.push(sc.clone());
+            }
+        }
+    }
+
+    let mut vanished = HashMap::new();
+    for id in load(app)?.snapshots.into_keys() {
+        if !participated_ids.contains(&id) {
+            let cancelled = SecondClass::get_by_id(service, &id)
+                .await
+                .ok()
+                .map(|detail| watch::is_cancelled(&detail));
+            vanished.insert(id, cancelled);
+        }
+    }
+
+    let Some(events) = modify(app, account.as_deref(), |state| {
+        state.apply(&fetched, &participated_ids, &vanished, now)
+    })?
+    else {
+        return Ok(Vec::new());
+    };
+    // 报名、取消与时间变更都会反映在最新列表中，直接按其重排提醒
+    let _ = crate::reminders::reschedule(app, &details);
+
+    for event in &events {
+        let _ = app.emit(CHANGE_EVENT, event);
+        let _ = app
+            .notification()
+            .builder()
+            .title("活动变更")
+            .body(event.message())
+            .show();
+    }
+    Ok(events)
+}
+
+/// 登录期间定时刷新；未登录时跳过。
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+            let service = app.state::<AppState>().session.current_service();
+            if let Some(service) = service {
+                let _ = refresh(&app, &service).await;
+            }
+        }
+    });
+}
+
+/// 清空本地数据（登出时调用）。
+pub fn clear(app: &AppHandle) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = app.store(WATCHER_STORE).context("Failed to access store")?;
+    store.clear();
+    store.save().context("Failed to save store")?;
+    Ok(())
+}
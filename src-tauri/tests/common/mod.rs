@@ -0,0 +1,24 @@
+// 离线测试共用的数据构造；每个测试文件只用到其中一部分。
+#![allow(dead_code)]
+
+use better_ustc_2_lib::rustustc::young::model::{CstDateTime, TimePeriod};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::{json, Value};
+
+/// 报名中的活动；`fields` 中的字段覆盖默认值，`itemName` 默认为「活动{id}」。
+pub fn activity(id: &str, fields: Value) -> SecondClass {
+    let mut raw = json!({
+        "id": id,
+        "itemName": format!("活动{}", id),
+        "itemStatus": 26,
+    });
+    if let (Value::Object(base), Value::Object(fields)) = (&mut raw, fields) {
+        base.extend(fields);
+    }
+    serde_json::from_value(raw).unwrap()
+}
+
+/// 北京时间 `YYYY-MM-DD HH:MM:SS`。
+pub fn at(s: &str) -> CstDateTime {
+    TimePeriod::parse(s).unwrap()
+}
@@ -0,0 +1,70 @@
+use better_ustc_2_lib::rustustc::young::appeal::MAX_ATTACHMENT_SIZE;
+use better_ustc_2_lib::rustustc::young::{Appeal, AppealStatus, SecondClass, Status};
+use serde_json::json;
+
+mod common;
+
+fn item(id: &str, status: Status) -> SecondClass {
+    common::activity(
+        id,
+        json!({
+            "itemStatus": status.code(),
+            "examineOpinion": "签到记录缺失",
+        }),
+    )
+}
+
+fn appeal(item_id: &str, status: i32) -> Appeal {
+    serde_json::from_value(json!({
+        "id": format!("a{}", item_id),
+        "itemId": item_id,
+        "appealReason": "已按时参加",
+        "examineStatus": status,
+        "examineOpinion": " 佐证不足 ",
+        "attachment": "group1/M00/a.jpg, group1/M00/b.jpg,",
+    }))
+    .unwrap()
+}
+
+#[test]
+fn pending_appeals_are_open_records() {
+    let appeals = vec![
+        appeal("1", 0),
+        appeal("2", 1),
+        appeal("3", 2),
+        appeal("4", 9),
+    ];
+    let ids: Vec<String> = Appeal::pending(&appeals)
+        .into_iter()
+        .filter_map(|a| a.item_id)
+        .collect();
+    assert_eq!(ids, vec!["1"]);
+
+    let rejected = item("5", Status::HourRejected);
+    assert_eq!(
+        rejected.hour_reject_reason().as_deref(),
+        Some("签到记录缺失")
+    );
+    assert_eq!(item("6", Status::HourPublic).hour_reject_reason(), None);
+
+    let a = &appeals[2];
+    assert_eq!(a.status(), AppealStatus::Rejected);
+    assert_eq!(a.opinion().as_deref(), Some("佐证不足"));
+    assert_eq!(
+        a.attachments(),
+        vec!["group1/M00/a.jpg", "group1/M00/b.jpg"]
+    );
+}
+
+#[test]
+fn appeal_validation() {
+    assert_eq!(Appeal::validate("  补签  ", &[]).unwrap(), "补签");
+    assert!(Appeal::validate("   ", &[]).is_err());
+    assert!(Appeal::validate(&"字".repeat(501), &[]).is_err());
+
+    let small = ("a.jpg".to_string(), vec![0u8; 16]);
+    assert!(Appeal::validate("理由", &vec![small.clone(); 3]).is_ok());
+    assert!(Appeal::validate("理由", &vec![small; 4]).is_err());
+    let big = ("b.pdf".to_string(), vec![0u8; MAX_ATTACHMENT_SIZE + 1]);
+    assert!(Appeal::validate("理由", &[big]).is_err());
+}
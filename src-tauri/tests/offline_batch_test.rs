@@ -0,0 +1,56 @@
+use better_ustc_2_lib::rustustc::young::batch::{classify_error, precheck, BatchOutcome};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::json;
+
+mod common;
+
+fn slot(id: &str, status: i32, st: &str, et: &str, num: i32) -> SecondClass {
+    common::activity(
+        id,
+        json!({
+            "itemStatus": status,
+            "st": st,
+            "et": et,
+            "applyNum": num,
+            "peopleNum": 30,
+        }),
+    )
+}
+
+#[test]
+fn precheck_reports_closed_full_and_conflicts() {
+    let a = slot("a", 26, "2025-12-21 19:00:00", "2025-12-21 21:00:00", 3);
+    let b = slot("b", 26, "2025-12-21 20:00:00", "2025-12-21 22:00:00", 3);
+    let full = slot("c", 26, "2025-12-22 19:00:00", "2025-12-22 21:00:00", 30);
+    let closed = slot("d", 28, "2025-12-23 19:00:00", "2025-12-23 21:00:00", 3);
+
+    assert_eq!(precheck(&a, &[]), None);
+    assert_eq!(precheck(&full, &[]), Some(BatchOutcome::Full));
+    assert_eq!(precheck(&closed, &[]), Some(BatchOutcome::Closed));
+
+    // 批次内靠前的活动已占用时间段
+    let taken = vec![("a".to_string(), a.hold_time().unwrap())];
+    assert_eq!(
+        precheck(&b, &taken),
+        Some(BatchOutcome::Conflict {
+            with: vec!["a".into()]
+        })
+    );
+    assert_eq!(precheck(&a, &taken), None);
+}
+
+#[test]
+fn errors_are_classified_by_message() {
+    assert_eq!(classify_error("报名人数已满"), BatchOutcome::Full);
+    assert_eq!(
+        classify_error("与已报名活动时间冲突"),
+        BatchOutcome::Conflict { with: vec![] }
+    );
+    assert_eq!(classify_error("报名已截止"), BatchOutcome::Closed);
+    assert_eq!(
+        classify_error("network down"),
+        BatchOutcome::Error {
+            message: "network down".into()
+        }
+    );
+}
@@ -0,0 +1,103 @@
+use better_ustc_2_lib::rustustc::young::checkin::CheckinError;
+use better_ustc_2_lib::rustustc::young::{CheckKind, CheckinPayload, SecondClass};
+use chrono::Duration;
+use serde_json::json;
+
+mod common;
+
+use common::at;
+
+fn concert(signin_closed: &str) -> SecondClass {
+    common::activity(
+        "7697fe4c",
+        json!({
+            "itemName": "古琴音乐会",
+            "itemStatus": 30,
+            "booleanRegistration": 1,
+            "qrSigninClosed": signin_closed,
+            "qrSignoffClosed": "0",
+            "st": "2025-12-21 19:00:00",
+            "et": "2025-12-21 21:00:00",
+        }),
+    )
+}
+
+#[test]
+fn parse_url_and_json_payloads() {
+    let p = CheckinPayload::parse(
+        "https://young.ustc.edu.cn/login/#/sign?itemId=7697fe4c&type=1&code=abc",
+    )
+    .unwrap();
+    assert_eq!(p.item_id, "7697fe4c");
+    assert_eq!(p.kind, CheckKind::SignIn);
+    assert_eq!(p.code.as_deref(), Some("abc"));
+    assert_eq!(p.issued_at, None);
+
+    let issued = at("2025-12-21 20:58:00");
+    let p = CheckinPayload::parse(
+        &json!({ "itemId": "7697fe4c", "type": "signOut", "t": issued.timestamp_millis() })
+            .to_string(),
+    )
+    .unwrap();
+    assert_eq!(p.kind, CheckKind::SignOut);
+    assert_eq!(p.issued_at, Some(issued));
+
+    assert_eq!(
+        CheckinPayload::parse("hello"),
+        Err(CheckinError::Unrecognized)
+    );
+    assert_eq!(
+        CheckinPayload::parse("https://x.cn/?type=1"),
+        Err(CheckinError::MissingItemId)
+    );
+    assert_eq!(
+        CheckinPayload::parse(r#"{"itemId": "1", "type": "3"}"#),
+        Err(CheckinError::UnknownKind)
+    );
+}
+
+#[test]
+fn validate_against_activity_and_window() {
+    let sc = concert("0");
+    let sign_in = CheckinPayload::parse("https://x.cn/?itemId=7697fe4c&type=1").unwrap();
+    let sign_out = CheckinPayload::parse("https://x.cn/?itemId=7697fe4c&type=2").unwrap();
+
+    assert!(sign_in.validate(&sc, at("2025-12-21 18:40:00")).is_ok());
+    assert!(matches!(
+        sign_in.validate(&sc, at("2025-12-21 18:20:00")),
+        Err(CheckinError::NotOpenYet { .. })
+    ));
+    assert!(matches!(
+        sign_in.validate(&sc, at("2025-12-21 21:10:00")),
+        Err(CheckinError::WindowClosed { .. })
+    ));
+    assert!(sign_out.validate(&sc, at("2025-12-21 21:50:00")).is_ok());
+
+    assert_eq!(
+        sign_in.validate(&concert("1"), at("2025-12-21 19:30:00")),
+        Err(CheckinError::Closed {
+            kind: CheckKind::SignIn
+        })
+    );
+
+    let other = CheckinPayload::parse("https://x.cn/?itemId=other&type=1").unwrap();
+    assert!(matches!(
+        other.validate(&sc, at("2025-12-21 19:30:00")),
+        Err(CheckinError::WrongActivity { .. })
+    ));
+
+    let mut unregistered = concert("0");
+    unregistered.boolean_registration = Some(0);
+    assert_eq!(
+        sign_in.validate(&unregistered, at("2025-12-21 19:30:00")),
+        Err(CheckinError::NotRegistered)
+    );
+
+    let mut stale = sign_in.clone();
+    stale.issued_at = Some(at("2025-12-21 19:30:00"));
+    assert!(stale.validate(&sc, at("2025-12-21 19:34:00")).is_ok());
+    assert_eq!(
+        stale.validate(&sc, at("2025-12-21 19:30:00") + Duration::minutes(6)),
+        Err(CheckinError::Expired)
+    );
+}
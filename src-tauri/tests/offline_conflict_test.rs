@@ -0,0 +1,204 @@
+use anyhow::{bail, Result};
+use better_ustc_2_lib::rustustc::young::conflict::{
+    resolve, ConflictAction, KeepReason, Registrar,
+};
+use better_ustc_2_lib::rustustc::young::model::{SignInfo, TimePeriod};
+use better_ustc_2_lib::rustustc::young::{ConflictPlan, ConflictPolicy, SecondClass};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+mod common;
+
+fn registered(id: &str, st: &str, et: &str, hours: f64) -> SecondClass {
+    common::activity(
+        id,
+        json!({
+            "st": st,
+            "et": et,
+            "validHour": hours,
+        }),
+    )
+}
+
+fn participated() -> Vec<SecondClass> {
+    vec![
+        registered("late", "2025-12-21 20:00:00", "2025-12-21 22:00:00", 1.0),
+        registered("early", "2025-12-21 18:00:00", "2025-12-21 19:30:00", 3.0),
+        registered("other", "2025-12-22 19:00:00", "2025-12-22 21:00:00", 1.0),
+    ]
+}
+
+fn actions(plan: &ConflictPlan) -> Vec<(&str, ConflictAction)> {
+    plan.conflicts
+        .iter()
+        .map(|c| (c.activity_id.as_str(), c.action.clone()))
+        .collect()
+}
+
+#[test]
+fn plan_lists_overlaps_in_start_order() {
+    let target = registered("t", "2025-12-21 19:00:00", "2025-12-21 21:00:00", 2.0);
+    let now = TimePeriod::parse("2025-12-20 12:00:00").unwrap();
+
+    let plan =
+        ConflictPlan::build(&target, &participated(), &ConflictPolicy::default(), now).unwrap();
+    assert!(plan.feasible);
+    assert_eq!(
+        actions(&plan),
+        vec![
+            ("early", ConflictAction::Cancel),
+            ("late", ConflictAction::Cancel)
+        ]
+    );
+
+    // 冲突活动已开始时不能取消
+    let now = TimePeriod::parse("2025-12-21 18:30:00").unwrap();
+    let plan =
+        ConflictPlan::build(&target, &participated(), &ConflictPolicy::default(), now).unwrap();
+    assert!(!plan.feasible);
+    assert_eq!(
+        plan.conflicts[0].action,
+        ConflictAction::Keep {
+            reason: KeepReason::Started
+        }
+    );
+}
+
+#[test]
+fn policy_limits_what_is_cancelled() {
+    let target = registered("t", "2025-12-21 19:00:00", "2025-12-21 21:00:00", 2.0);
+    let now = TimePeriod::parse("2025-12-20 12:00:00").unwrap();
+
+    let policy: ConflictPolicy =
+        serde_json::from_value(json!({ "only_lower_hours": true })).unwrap();
+    let plan = ConflictPlan::build(&target, &participated(), &policy, now).unwrap();
+    assert!(!plan.feasible);
+    assert_eq!(
+        actions(&plan),
+        vec![
+            (
+                "early",
+                ConflictAction::Keep {
+                    reason: KeepReason::MoreHours
+                }
+            ),
+            ("late", ConflictAction::Cancel)
+        ]
+    );
+
+    let policy: ConflictPolicy =
+        serde_json::from_value(json!({ "protected_ids": ["late"], "max_cancel": 1 })).unwrap();
+    let plan = ConflictPlan::build(&target, &participated(), &policy, now).unwrap();
+    assert_eq!(plan.to_cancel().count(), 1);
+    assert_eq!(
+        plan.conflicts[1].action,
+        ConflictAction::Keep {
+            reason: KeepReason::Protected
+        }
+    );
+}
+
+#[test]
+fn empty_plan_is_not_feasible() {
+    let target = registered("t", "2025-12-23 19:00:00", "2025-12-23 21:00:00", 2.0);
+    let now = TimePeriod::parse("2025-12-20 12:00:00").unwrap();
+
+    let plan =
+        ConflictPlan::build(&target, &participated(), &ConflictPolicy::default(), now).unwrap();
+    assert!(plan.conflicts.is_empty());
+    assert!(!plan.feasible);
+    assert!(plan.reason.is_some());
+}
+
+/// 记录调用的假报名操作：目标活动报名总是失败，恢复时需要报名信息
+#[derive(Default)]
+struct FailingRegistrar {
+    calls: Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl Registrar for FailingRegistrar {
+    async fn cancel(&self, activity_id: &str) -> Result<()> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("cancel {}", activity_id));
+        Ok(())
+    }
+
+    async fn apply(
+        &self,
+        target: &SecondClass,
+        _force: bool,
+        _sign_info: Option<SignInfo>,
+    ) -> Result<bool> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("apply {}", target.id));
+        bail!("quota full")
+    }
+
+    async fn reapply(&self, activity_id: &str, sign_info: Option<SignInfo>) -> Result<bool> {
+        let remarks = sign_info
+            .as_ref()
+            .map(|i| i.remarks.clone())
+            .unwrap_or_default();
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("reapply {} {}", activity_id, remarks));
+        Ok(sign_info.is_none())
+    }
+}
+
+#[tokio::test]
+async fn failed_apply_restores_cancelled_with_recorded_info() {
+    let target = registered("t", "2025-12-21 19:00:00", "2025-12-21 21:00:00", 2.0);
+    let now = TimePeriod::parse("2025-12-20 12:00:00").unwrap();
+    let plan =
+        ConflictPlan::build(&target, &participated(), &ConflictPolicy::default(), now).unwrap();
+
+    let recorded = HashMap::from([(
+        "early".to_string(),
+        SignInfo {
+            college: "少年班学院".into(),
+            classes: "1".into(),
+            phone: "13800000000".into(),
+            email: "a@mail.ustc.edu.cn".into(),
+            remarks: "素食".into(),
+        },
+    )]);
+    let ops = FailingRegistrar::default();
+    let outcome = resolve(&ops, &target, &plan, false, None, &recorded)
+        .await
+        .unwrap();
+
+    assert!(!outcome.applied);
+    assert_eq!(outcome.error.as_deref(), Some("quota full"));
+    assert_eq!(outcome.cancelled, vec!["early", "late"]);
+    assert_eq!(outcome.restored, vec!["early", "late"]);
+    assert_eq!(outcome.restored_with_default_info, vec!["late"]);
+    assert!(outcome.summary().contains("default sign-up info: late"));
+    assert_eq!(
+        *ops.calls.lock().unwrap(),
+        vec![
+            "cancel early",
+            "cancel late",
+            "apply t",
+            "reapply early 素食",
+            "reapply late ",
+        ]
+    );
+
+    // 不可行的计划不做任何修改
+    let now = TimePeriod::parse("2025-12-21 18:30:00").unwrap();
+    let plan =
+        ConflictPlan::build(&target, &participated(), &ConflictPolicy::default(), now).unwrap();
+    let ops = FailingRegistrar::default();
+    assert!(resolve(&ops, &target, &plan, false, None, &recorded)
+        .await
+        .is_err());
+    assert!(ops.calls.lock().unwrap().is_empty());
+}
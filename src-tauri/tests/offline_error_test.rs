@@ -0,0 +1,64 @@
+use anyhow::Context;
+use better_ustc_2_lib::rustustc::error::{AppError, CasError, YouthApiError};
+use better_ustc_2_lib::rustustc::young::Query;
+use serde_json::json;
+
+fn api(code: i64, message: &str) -> anyhow::Error {
+    YouthApiError::from_response(&json!({ "success": false, "code": code, "message": message }))
+        .into()
+}
+
+#[test]
+fn anyhow_errors_are_classified_through_the_chain() {
+    let full = AppError::from(api(500, "报名人数已满").context("Failed to apply"));
+    assert_eq!(full, AppError::ActivityFull("报名人数已满".into()));
+
+    let conflict = AppError::from(api(500, "与已报名活动时间冲突"));
+    assert_eq!(conflict.code(), "TIME_CONFLICT");
+    assert_eq!(
+        AppError::from(api(401, "Token失效")).code(),
+        "SESSION_EXPIRED"
+    );
+    assert_eq!(
+        AppError::from(api(500, "活动不存在")).code(),
+        "YOUTH_API_ERROR"
+    );
+
+    let cas = AppError::from(anyhow::Error::new(CasError("密码错误".into())));
+    assert_eq!(cas, AppError::Cas("密码错误".into()));
+
+    let parse = serde_json::from_str::<serde_json::Value>("{")
+        .context("Failed to parse getUser JSON")
+        .unwrap_err();
+    assert_eq!(AppError::from(parse).code(), "PARSE_ERROR");
+
+    let other = AppError::from(anyhow::anyhow!("boom").context("while syncing"));
+    assert_eq!(other, AppError::Internal("while syncing: boom".into()));
+    assert!(!other.retryable());
+    assert!(AppError::Network("timeout".into()).retryable());
+}
+
+#[test]
+fn errors_serialize_with_code_message_and_detail() {
+    let err = AppError::rule("INVALID_SIGN_INFO", "Sign info validation failed")
+        .with_detail(json!({ "fields": ["phone"], "code": "ignored" }));
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        json!({
+            "code": "INVALID_SIGN_INFO",
+            "message": "Sign info validation failed",
+            "retryable": false,
+            "fields": ["phone"],
+        })
+    );
+
+    let query = AppError::from(Query::parse("module:").unwrap_err());
+    let v = serde_json::to_value(&query).unwrap();
+    assert_eq!(v["code"], "QUERY_SYNTAX");
+    assert!(v["span"]["start"].is_number());
+
+    assert_eq!(
+        serde_json::to_value(AppError::AuthRequired).unwrap(),
+        json!({ "code": "AUTH_REQUIRED", "message": "Please login first", "retryable": false })
+    );
+}
@@ -0,0 +1,126 @@
+use better_ustc_2_lib::rustustc::error::AppError;
+use better_ustc_2_lib::rustustc::young::evaluation::{
+    awaiting_evaluation, AnswerValue, Question, QuestionKind,
+};
+use better_ustc_2_lib::rustustc::young::model::cst;
+use better_ustc_2_lib::rustustc::young::{Answer, EvaluationForm, SecondClass};
+use chrono::NaiveDateTime;
+use serde_json::json;
+
+fn form() -> EvaluationForm {
+    let raw = json!([
+        { "id": "q1", "title": "整体满意度", "type": "1", "required": 1 },
+        { "id": "q2", "title": "获知渠道", "type": "2", "required": "1",
+          "options": [{ "value": "a", "text": "公众号" }, { "value": "b", "text": "班群" }] },
+        { "id": "q3", "title": "收获", "type": "3",
+          "options": [{ "id": "x" }, { "id": "y" }] },
+        { "id": "q4", "title": "建议", "type": "4", "maxLength": 10 }
+    ]);
+    EvaluationForm {
+        item_id: "item".into(),
+        questions: raw
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|q| Question::from_raw(q).unwrap())
+            .collect(),
+    }
+}
+
+fn answer(id: &str, value: AnswerValue) -> Answer {
+    Answer {
+        question_id: id.into(),
+        value,
+    }
+}
+
+#[test]
+fn evaluation_form_validation() {
+    let form = form();
+    assert_eq!(form.questions.len(), 4);
+    assert!(matches!(
+        form.questions[0].kind,
+        QuestionKind::Rating { max: 5 }
+    ));
+    assert!(form.questions[1].required && !form.questions[2].required);
+
+    let ok = vec![
+        answer("q1", AnswerValue::Rating(5)),
+        answer("q2", AnswerValue::Choice(vec!["b".into()])),
+        answer("q3", AnswerValue::Choice(vec!["x".into(), "y".into()])),
+    ];
+    assert!(form.validate(&ok).is_empty());
+
+    let bad = vec![
+        answer("q1", AnswerValue::Rating(6)),
+        answer("q2", AnswerValue::Choice(vec!["a".into(), "b".into()])),
+        answer("q3", AnswerValue::Choice(vec!["z".into()])),
+        answer("q4", AnswerValue::Text("太长了".repeat(4))),
+        answer("q9", AnswerValue::Text("多余".into())),
+    ];
+    let errors: Vec<(String, String)> = form
+        .validate(&bad)
+        .into_iter()
+        .map(|e| (e.question_id, e.message))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            ("q9".to_string(), "题目不存在".to_string()),
+            ("q1".to_string(), "评分应在 1 到 5 之间".to_string()),
+            ("q2".to_string(), "只能选择一项".to_string()),
+            ("q3".to_string(), "选项无效".to_string()),
+            ("q4".to_string(), "不能超过 10 字".to_string()),
+        ]
+    );
+
+    // 必填题缺失、答案类型不符
+    let errors = form.validate(&[answer("q1", AnswerValue::Text("5".into()))]);
+    let ids: Vec<&str> = errors.iter().map(|e| e.question_id.as_str()).collect();
+    assert_eq!(ids, vec!["q1", "q2"]);
+
+    let json = serde_json::to_value(&ok[0]).unwrap();
+    assert_eq!(
+        json,
+        json!({ "question_id": "q1", "value": { "type": "rating", "value": 5 } })
+    );
+}
+
+#[test]
+fn unknown_question_type_is_an_error() {
+    let err = Question::from_raw(&json!({ "id": "q5", "title": "未知题型", "type": "9" }))
+        .map_err(AppError::from)
+        .unwrap_err();
+    assert_eq!(err.code(), "PARSE_ERROR");
+    assert!(err.message().contains("\"9\""));
+
+    assert!(Question::from_raw(&json!({ "title": "缺少 ID", "type": "1" })).is_err());
+    assert!(Question::from_raw(&json!({ "id": "q6", "title": "缺少题型" })).is_err());
+}
+
+#[test]
+fn awaiting_evaluation_requires_ended_and_unevaluated() {
+    let sc = |evaluation: &str, evaluated: i32, et: &str| -> SecondClass {
+        serde_json::from_value(json!({
+            "id": "1", "itemName": "讲座", "itemStatus": 40,
+            "evaluation": evaluation, "evaluated": evaluated,
+            "st": "2025-12-01 19:00:00", "et": et,
+        }))
+        .unwrap()
+    };
+    let now =
+        cst(NaiveDateTime::parse_from_str("2025-12-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap());
+    assert!(awaiting_evaluation(&sc("1", 0, "2025-12-01 21:00:00"), now));
+    assert!(!awaiting_evaluation(
+        &sc("1", 1, "2025-12-01 21:00:00"),
+        now
+    ));
+    assert!(!awaiting_evaluation(
+        &sc("0", 0, "2025-12-01 21:00:00"),
+        now
+    ));
+    assert!(!awaiting_evaluation(
+        &sc("1", 0, "2025-12-20 21:00:00"),
+        now
+    ));
+}
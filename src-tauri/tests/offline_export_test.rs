@@ -0,0 +1,65 @@
+use better_ustc_2_lib::export::format::{to_csv, to_ics, to_json};
+use better_ustc_2_lib::rustustc::young::{SecondClass, Status};
+use serde_json::json;
+
+mod common;
+
+fn finished(name: &str) -> SecondClass {
+    common::activity(
+        "7697fe4c",
+        json!({
+            "itemName": name,
+            "itemStatus": Status::Finished.code(),
+            "validHour": 2.5,
+            "module": "m",
+            "module_dictText": "美",
+            "businessDeptId": "d1",
+            "businessDeptName": "学生正则古琴协会",
+            "organizer_dictText": "学生正则古琴协会",
+            "placeInfo": "东区水上报告厅",
+            "st": "2025-12-21 19:00:00",
+            "et": "2025-12-21 21:00:00",
+        }),
+    )
+}
+
+#[test]
+fn csv_and_json_transcripts() {
+    let list = vec![finished("「聆冬映雪」古琴音乐会, 第一场")];
+
+    let csv = to_csv(&list);
+    let mut lines = csv.trim_start_matches('\u{feff}').lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "活动名称,模块,组织单位,开始时间,结束时间,学时,状态"
+    );
+    assert_eq!(
+        lines.next().unwrap(),
+        "\"「聆冬映雪」古琴音乐会, 第一场\",美,学生正则古琴协会,2025-12-21 19:00,2025-12-21 21:00,2.5,结项"
+    );
+
+    let rows: serde_json::Value = serde_json::from_str(&to_json(&list).unwrap()).unwrap();
+    assert_eq!(rows[0]["module"], "美");
+    assert_eq!(rows[0]["hours"], 2.5);
+    assert_eq!(rows[0]["start"], "2025-12-21 19:00");
+}
+
+#[test]
+fn ics_events_are_escaped_and_folded() {
+    let long_name = "古琴音乐会;".repeat(10);
+    let ics = to_ics(&[finished(&long_name)], &[30, 1440]);
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains("DTSTART;TZID=Asia/Shanghai:20251221T190000\r\n"));
+    assert!(ics.contains("DTEND;TZID=Asia/Shanghai:20251221T210000\r\n"));
+    assert!(ics.contains("LOCATION:东区水上报告厅\r\n"));
+    assert!(ics.contains("ORGANIZER;CN=学生正则古琴协会:invalid:nomail\r\n"));
+    assert!(ics.contains("TRIGGER:-PT30M\r\n"));
+    assert!(ics.contains("TRIGGER:-PT1440M\r\n"));
+
+    // 每个物理行不超过 75 字节；展开折行后恢复原文
+    assert!(ics.split("\r\n").all(|l| l.len() <= 75));
+    let unfolded = ics.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!("SUMMARY:{}", "古琴音乐会\\;".repeat(10))));
+}
@@ -0,0 +1,113 @@
+use better_ustc_2_lib::rustustc::error::AppError;
+use better_ustc_2_lib::rustustc::young::favorite::{
+    should_queue, transient, FavoriteCache, PendingOp,
+};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::json;
+
+mod common;
+
+fn item(id: &str, enshrine: Option<i32>) -> SecondClass {
+    common::activity(
+        id,
+        json!({
+            "enshrine": enshrine,
+        }),
+    )
+}
+
+#[test]
+fn offline_ops_override_server_flag() {
+    let mut cache = FavoriteCache::default();
+    let a = item("a", None);
+    let b = item("b", Some(1));
+    assert!(!cache.is_favorite(&a));
+    assert!(cache.is_favorite(&b));
+
+    cache.record(&a, true);
+    cache.record(&b, false);
+    // 同一活动只保留最后一次操作
+    cache.record(&a, false);
+    cache.record(&a, true);
+    assert_eq!(
+        cache.pending,
+        vec![
+            PendingOp {
+                id: "b".into(),
+                favorite: false
+            },
+            PendingOp {
+                id: "a".into(),
+                favorite: true
+            },
+        ]
+    );
+    assert!(cache.is_favorite(&a));
+    assert!(!cache.is_favorite(&b));
+
+    let list = cache.annotate_all([&a, &b]);
+    assert_eq!(list[0]["is_favorite"], true);
+    assert_eq!(list[1]["is_favorite"], false);
+    assert_eq!(list[0]["id"], "a");
+}
+
+#[test]
+fn remote_list_merges_pending() {
+    let mut cache = FavoriteCache::default();
+    cache.record(&item("local", None), true);
+    cache.record(&item("gone", Some(1)), false);
+
+    cache.replace_remote(vec![item("gone", Some(1)), item("server", Some(1))]);
+    let ids: Vec<&str> = cache.items.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec!["server", "local"]);
+
+    // 补发成功后，服务器列表成为唯一来源
+    cache.confirm(&item("local", None), true);
+    cache.confirm(&item("gone", None), false);
+    assert!(cache.pending.is_empty());
+    cache.replace_remote(vec![item("server", Some(1))]);
+    assert_eq!(cache.items.len(), 1);
+}
+
+#[test]
+fn only_transient_failures_are_queued() {
+    assert!(should_queue(&AppError::Network("timeout".into())));
+    assert!(should_queue(&AppError::SessionExpired("Token失效".into())));
+    assert!(!transient(&AppError::Youth("活动不存在".into())));
+
+    // 拿不到详情时只记录操作，不缓存没有名称的快照
+    let mut cache = FavoriteCache::default();
+    cache.record(&item("kept", None), true);
+    cache.record_id("kept", true);
+    cache.record_id("new", true);
+    let ids: Vec<&str> = cache.items.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec!["kept"]);
+    assert_eq!(cache.items[0].name, "活动kept");
+    assert_eq!(cache.pending.len(), 2);
+
+    cache.record_id("kept", false);
+    assert!(cache.items.is_empty());
+    assert!(!cache.is_favorite(&item("kept", Some(1))));
+}
+
+#[test]
+fn unavailable_endpoint_is_queued() {
+    // 推测的收藏接口不存在：Jeecg 的 404 响应或 HTML 错误页
+    assert!(should_queue(&AppError::Youth("".into())));
+    assert!(should_queue(&AppError::Parse(
+        "expected value at line 1".into()
+    )));
+    assert!(!should_queue(&AppError::ActivityFull("人数已满".into())));
+    assert!(!should_queue(&AppError::rule("BAD_REQUEST", "x")));
+
+    let mut cache = FavoriteCache::default();
+    cache.record(&item("a", None), true);
+    assert!(cache.is_favorite(&item("a", None)));
+    assert_eq!(
+        cache.pending,
+        vec![PendingOp {
+            id: "a".into(),
+            favorite: true
+        }]
+    );
+}
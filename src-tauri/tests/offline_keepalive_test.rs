@@ -0,0 +1,73 @@
+use better_ustc_2_lib::rustustc::young::keepalive::{
+    decide, Health, KeepAliveAction, KeepAliveSettings,
+};
+use std::time::Duration;
+
+const MIN: u64 = 60;
+
+#[test]
+fn decide_refreshes_before_expiry_and_relogs_only_when_cas_is_gone() {
+    let settings = KeepAliveSettings::default();
+    let fresh = Duration::from_secs(10 * MIN);
+    let old = Duration::from_secs(90 * MIN);
+
+    assert_eq!(
+        decide(Health::Valid, Health::Valid, fresh, &settings),
+        KeepAliveAction::Healthy
+    );
+    // token 快到期时主动刷新
+    assert_eq!(
+        decide(Health::Valid, Health::Valid, old, &settings),
+        KeepAliveAction::Refresh
+    );
+    assert_eq!(
+        decide(Health::Valid, Health::Expired, fresh, &settings),
+        KeepAliveAction::Refresh
+    );
+    assert_eq!(
+        decide(Health::Expired, Health::Valid, fresh, &settings),
+        KeepAliveAction::Relogin
+    );
+    // 离线时无法判断，不做任何修改
+    assert_eq!(
+        decide(Health::Offline, Health::Expired, old, &settings),
+        KeepAliveAction::Backoff
+    );
+    assert_eq!(
+        decide(Health::Expired, Health::Offline, fresh, &settings),
+        KeepAliveAction::Backoff
+    );
+}
+
+#[test]
+fn backoff_doubles_up_to_the_limit_and_settings_are_validated() {
+    let settings = KeepAliveSettings::default();
+    assert_eq!(settings.next_delay(0), Duration::from_secs(10 * MIN));
+    assert_eq!(settings.next_delay(1), Duration::from_secs(20 * MIN));
+    assert_eq!(settings.next_delay(2), Duration::from_secs(40 * MIN));
+    assert_eq!(settings.next_delay(3), Duration::from_secs(60 * MIN));
+    assert_eq!(settings.next_delay(u32::MAX), Duration::from_secs(60 * MIN));
+
+    assert!(settings.validate().is_ok());
+    let zero = KeepAliveSettings {
+        interval_minutes: 0,
+        ..settings.clone()
+    };
+    assert!(zero.validate().is_err());
+    let refresh_too_soon = KeepAliveSettings {
+        refresh_after_minutes: 5,
+        ..settings.clone()
+    };
+    assert!(refresh_too_soon.validate().is_err());
+    let backoff_too_short = KeepAliveSettings {
+        max_backoff_minutes: 5,
+        ..settings
+    };
+    assert!(backoff_too_short.validate().is_err());
+
+    // 旧版本保存的设置缺少字段时使用默认值
+    let partial: KeepAliveSettings =
+        serde_json::from_value(serde_json::json!({ "enabled": false })).unwrap();
+    assert!(!partial.enabled);
+    assert_eq!(partial.interval_minutes, 10);
+}
@@ -0,0 +1,138 @@
+use better_ustc_2_lib::ledger::{semester_of, HourLedger, HourRequirements, ModuleRequirement};
+use better_ustc_2_lib::rustustc::young::{SecondClass, Status};
+use serde_json::json;
+
+mod common;
+
+use common::at;
+
+fn record(id: &str, module: &str, dept: &str, status: Status, hours: f64, st: &str) -> SecondClass {
+    common::activity(
+        id,
+        json!({
+            "itemStatus": status.code(),
+            "validHour": hours,
+            "module": module,
+            "module_dictText": module,
+            "businessDeptId": dept,
+            "businessDeptName": dept,
+            "st": st,
+            "et": st,
+        }),
+    )
+}
+
+#[test]
+fn ledger_counts_only_granted_hours() {
+    let participated = vec![
+        record(
+            "1",
+            "美",
+            "古琴协会",
+            Status::HourApproved,
+            2.0,
+            "2025-10-10 19:00:00",
+        ),
+        record(
+            "2",
+            "美",
+            "古琴协会",
+            Status::Finished,
+            1.5,
+            "2026-03-01 19:00:00",
+        ),
+        record(
+            "3",
+            "体",
+            "体育部",
+            Status::Finished,
+            3.0,
+            "2026-03-02 19:00:00",
+        ),
+        record(
+            "4",
+            "德",
+            "团委",
+            Status::HourApplying,
+            1.0,
+            "2026-03-03 19:00:00",
+        ),
+        record(
+            "5",
+            "德",
+            "团委",
+            Status::Applying,
+            9.0,
+            "2026-03-04 19:00:00",
+        ),
+        record(
+            "6",
+            "劳",
+            "后勤",
+            Status::Finished,
+            1.0,
+            "2026-03-05 19:00:00",
+        ),
+    ];
+    let ledger = HourLedger::from_participated(&participated);
+    assert_eq!(ledger.entries.len(), 4);
+    assert_eq!(ledger.pending.len(), 1);
+
+    let requirements = HourRequirements {
+        modules: vec![
+            ModuleRequirement {
+                module: "德".into(),
+                hours: 2.0,
+            },
+            ModuleRequirement {
+                module: "体".into(),
+                hours: 2.0,
+            },
+            ModuleRequirement {
+                module: "美".into(),
+                hours: 4.0,
+            },
+        ],
+        total: Some(8.0),
+    };
+    let summary = ledger.summary(&requirements);
+
+    let names: Vec<&str> = summary.modules.iter().map(|m| m.module.as_str()).collect();
+    assert_eq!(names, vec!["德", "体", "美", "劳"]);
+
+    let de = &summary.modules[0];
+    assert_eq!((de.earned, de.deficit, de.pending), (0.0, 2.0, 1.0));
+    let ti = &summary.modules[1];
+    assert!(ti.completed && ti.deficit == 0.0);
+    let mei = &summary.modules[2];
+    assert_eq!((mei.earned, mei.deficit, mei.activity_count), (3.5, 0.5, 2));
+
+    // 总学时 7.5 已接近 8，但德、美两个模块的差距不能由体育超出部分抵扣
+    assert_eq!(summary.total_earned, 7.5);
+    assert_eq!(summary.total_deficit, 2.5);
+    assert_eq!(summary.total_pending, 1.0);
+
+    assert_eq!(summary.by_semester[0].key, "2025-2026 春季学期");
+    assert_eq!(summary.by_semester[0].hours, 5.5);
+    assert_eq!(summary.by_department[0].key, "古琴协会");
+}
+
+#[test]
+fn semester_boundaries() {
+    assert_eq!(
+        semester_of(&at("2025-09-01 00:00:00")),
+        "2025-2026 秋季学期"
+    );
+    assert_eq!(
+        semester_of(&at("2026-01-15 12:00:00")),
+        "2025-2026 秋季学期"
+    );
+    assert_eq!(
+        semester_of(&at("2026-02-20 12:00:00")),
+        "2025-2026 春季学期"
+    );
+    assert_eq!(
+        semester_of(&at("2026-07-10 12:00:00")),
+        "2025-2026 夏季学期"
+    );
+}
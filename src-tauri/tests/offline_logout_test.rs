@@ -0,0 +1,45 @@
+use anyhow::anyhow;
+use better_ustc_2_lib::logout::report::{account_changed, LogoutReport};
+
+#[test]
+fn report_records_every_step_and_fails_if_any_does() {
+    let mut report = LogoutReport::default();
+    assert!(report.ok);
+
+    report.record("cas_logout", Ok(()));
+    report.record(
+        "credentials",
+        Err(anyhow!("disk full").context("Failed to save store")),
+    );
+    report.record("waitlist", Ok(()));
+
+    assert!(!report.ok);
+    let steps: Vec<_> = report.steps.iter().map(|s| (s.step, s.ok)).collect();
+    assert_eq!(
+        steps,
+        vec![
+            ("cas_logout", true),
+            ("credentials", false),
+            ("waitlist", true)
+        ]
+    );
+    // 错误保留完整上下文
+    assert_eq!(
+        report.steps[1].error.as_deref(),
+        Some("Failed to save store: disk full")
+    );
+    assert_eq!(report.failed().count(), 1);
+
+    let v = serde_json::to_value(&report).unwrap();
+    assert_eq!(v["ok"], false);
+    assert_eq!(v["steps"][0]["step"], "cas_logout");
+    assert!(v["steps"][0]["error"].is_null());
+}
+
+#[test]
+fn data_is_wiped_only_when_another_account_signs_in() {
+    assert!(!account_changed(None, "PB21000001"));
+    assert!(!account_changed(Some("PB21000001"), "PB21000001"));
+    assert!(!account_changed(Some("pb21000001"), " PB21000001 "));
+    assert!(account_changed(Some("PB21000001"), "PB21000002"));
+}
@@ -1,5 +1,5 @@
 use better_ustc_2_lib::rustustc::url::generate_url;
-use better_ustc_2_lib::rustustc::young::model::{Label, Module, TimePeriod};
+use better_ustc_2_lib::rustustc::young::model::{Label, Module, SignInfo, TimePeriod};
 use better_ustc_2_lib::rustustc::young::{SCFilter, SecondClass, Status};
 use chrono::NaiveDateTime;
 use serde_json::json;
@@ -36,13 +36,13 @@ fn sample_activity() -> SecondClass {
 
 #[test]
 fn time_period_overlap_and_contain() {
-    let a = TimePeriod::new(
+    let a = TimePeriod::from_naive(
         NaiveDateTime::parse_from_str("2024-03-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
         NaiveDateTime::parse_from_str("2024-03-01 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
     )
     .unwrap();
 
-    let b = TimePeriod::new(
+    let b = TimePeriod::from_naive(
         NaiveDateTime::parse_from_str("2024-03-01 11:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
         NaiveDateTime::parse_from_str("2024-03-01 11:30:00", "%Y-%m-%d %H:%M:%S").unwrap(),
     )
@@ -51,7 +51,7 @@ fn time_period_overlap_and_contain() {
     assert!(a.is_overlap(&b));
     assert!(a.is_contain(&b));
 
-    let c = TimePeriod::new(
+    let c = TimePeriod::from_naive(
         NaiveDateTime::parse_from_str("2024-03-01 12:30:00", "%Y-%m-%d %H:%M:%S").unwrap(),
         NaiveDateTime::parse_from_str("2024-03-01 13:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
     )
@@ -85,7 +85,7 @@ fn scfilter_check_and_applyable() {
     assert!(filter.check(&activity, false));
 
     // 时间段严格包含：活动在 18-20 点，过滤 17-21 点应通过
-    let time_filter = TimePeriod::new(
+    let time_filter = TimePeriod::from_naive(
         NaiveDateTime::parse_from_str("2024-02-10 17:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
         NaiveDateTime::parse_from_str("2024-02-10 21:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
     )
@@ -93,7 +93,7 @@ fn scfilter_check_and_applyable() {
     let filter = SCFilter {
         fuzzy_name: true,
         strict_time: true,
-        time_period: Some(time_filter.clone()),
+        time_period: Some(time_filter),
         ..SCFilter::default()
     };
     let ht = activity.hold_time().expect("hold_time should parse");
@@ -118,3 +118,26 @@ fn url_generation() {
     let url = generate_url("young", "item/scItem/enrolmentList");
     assert!(url.starts_with("https://young.ustc.edu.cn/item/scItem/enrolmentList"));
 }
+
+#[test]
+fn sign_info_validation() {
+    let info = SignInfo {
+        college: " 计算机科学与技术学院 ".into(),
+        classes: "PB23051".into(),
+        phone: "+86 138-0013-8000".into(),
+        email: "".into(),
+        remarks: "".into(),
+    };
+    assert!(info.validate().is_empty());
+    assert_eq!(info.normalized().phone, "13800138000");
+
+    let bad = SignInfo {
+        college: "".into(),
+        classes: "PB23051".into(),
+        phone: "12345".into(),
+        email: "not-an-email".into(),
+        remarks: "".into(),
+    };
+    let fields: Vec<&str> = bad.validate().iter().map(|e| e.field).collect();
+    assert_eq!(fields, vec!["college", "phone", "email"]);
+}
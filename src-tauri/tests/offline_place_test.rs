@@ -0,0 +1,97 @@
+use better_ustc_2_lib::recommend::Recommender;
+use better_ustc_2_lib::rustustc::young::place::{places, CampusRule};
+use better_ustc_2_lib::rustustc::young::{
+    Campus, CampusDictionary, Query, SCFilter, SecondClass, TagIndex,
+};
+use serde_json::{json, Value};
+
+mod common;
+
+fn venue(place_info: &str, places: Value, st: &str) -> SecondClass {
+    common::activity(
+        "p1",
+        json!({
+            "itemName": "古琴音乐会",
+            "placeInfo": place_info,
+            "itemPlaceDTO": { "places": places },
+            "st": st,
+            "et": "2025-12-22 21:00:00",
+        }),
+    )
+}
+
+#[test]
+fn venues_and_campus_classification() {
+    let dict = CampusDictionary::default();
+    assert_eq!(dict.classify("东区水上报告厅"), Some(Campus::East));
+    assert_eq!(dict.classify("西活二楼"), Some(Campus::West));
+    assert_eq!(dict.classify("高新区师生活动中心"), Some(Campus::HighTech));
+    // 显式校区名优先于地标关键词
+    assert_eq!(dict.classify("西区学生活动中心"), Some(Campus::West));
+    assert_eq!(dict.classify("腾讯会议"), None);
+    assert_eq!(Campus::parse("高新"), Some(Campus::HighTech));
+    assert_eq!(Campus::parse("east"), Some(Campus::East));
+
+    let sc = venue(
+        "东区水上报告厅",
+        json!([
+            { "placeInfo": "东区水上报告厅", "placeSt": "2025-12-21 19:00:00", "placeEt": null },
+            { "placeInfo": "高新区会议室", "placeSt": 1766397600000i64, "placeEt": 1766404800000i64 },
+            { "placeInfo": "  " },
+        ]),
+        "2025-12-21 19:00:00",
+    );
+    let ps = places(&sc, &dict);
+    assert_eq!(ps.len(), 2);
+    assert_eq!(ps[0].campus, Some(Campus::East));
+    assert_eq!(ps[0].start.unwrap().to_string(), "2025-12-21 19:00:00 CST");
+    assert!(ps[0].end.is_none());
+    assert_eq!(ps[1].campus, Some(Campus::HighTech));
+    assert_eq!(ps[1].start.unwrap().to_string(), "2025-12-22 18:00:00 CST");
+    assert_eq!(dict.campuses(&sc), vec![Campus::East, Campus::HighTech]);
+
+    // 没有 places 时回退到 placeInfo；自定义字典
+    let sc = venue("一教 1101", Value::Null, "2025-12-21 19:00:00");
+    assert_eq!(places(&sc, &dict)[0].campus, None);
+    let custom = CampusDictionary {
+        rules: vec![CampusRule {
+            campus: Campus::East,
+            keywords: vec!["一教".into()],
+        }],
+    };
+    assert_eq!(custom.campuses(&sc), vec![Campus::East]);
+}
+
+#[test]
+fn filter_and_recommender_use_campus() {
+    // 2025-12-22 为周一
+    let east_evening = venue("东区水上报告厅", Value::Null, "2025-12-22 19:00:00");
+    let east_weekend = venue("东区水上报告厅", Value::Null, "2025-12-20 19:00:00");
+    let online = venue("腾讯会议", Value::Null, "2025-12-22 19:00:00");
+
+    let filter = SCFilter::new().add_campus(Campus::HighTech);
+    assert!(!filter.check(&east_evening, false));
+    assert!(filter.check(&online, false));
+
+    let filter = Query::parse("campus:东")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap();
+    assert!(filter.check(&east_evening, false));
+    let filter = Query::parse("-campus:东区")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap();
+    assert!(!filter.check(&east_evening, false));
+    assert!(Query::parse("campus:火星")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .is_err());
+
+    let dict = CampusDictionary::default();
+    let penalty = |sc: &SecondClass, home| Recommender::campus_penalty(sc, home, &dict);
+    assert_eq!(penalty(&east_evening, Campus::East), 0.0);
+    assert_eq!(penalty(&online, Campus::HighTech), 0.0);
+    assert!(penalty(&east_evening, Campus::HighTech) > penalty(&east_weekend, Campus::HighTech));
+    assert!(penalty(&east_weekend, Campus::HighTech) > 0.0);
+}
@@ -0,0 +1,194 @@
+use better_ustc_2_lib::rustustc::young::model::{CmpOp, Department, Label, Module};
+use better_ustc_2_lib::rustustc::young::query::Span;
+use better_ustc_2_lib::rustustc::young::{Query, SecondClass, Status, TagIndex, TagKind};
+use serde_json::json;
+
+fn modules() -> Vec<Module> {
+    vec![
+        Module {
+            value: "m".into(),
+            text: "美".into(),
+        },
+        Module {
+            value: "t".into(),
+            text: "体".into(),
+        },
+    ]
+}
+
+fn departments() -> Vec<Department> {
+    let child = |id: &str, name: &str| Department {
+        id: id.into(),
+        name: name.into(),
+        children: vec![],
+        level: 1,
+    };
+    vec![Department {
+        id: "root".into(),
+        name: "中国科学技术大学".into(),
+        children: vec![
+            child("dept-qin", "学生正则古琴协会"),
+            child("dept-art", "艺术中心"),
+            child("dept-art2", "艺术教学中心"),
+        ],
+        level: 0,
+    }]
+}
+
+fn labels() -> Vec<Label> {
+    vec![
+        Label {
+            id: "lab-online".into(),
+            name: "线上".into(),
+        },
+        Label {
+            id: "lab-show".into(),
+            name: "演出".into(),
+        },
+    ]
+}
+
+fn index() -> TagIndex {
+    TagIndex::new(modules(), departments(), labels())
+}
+
+fn concert() -> SecondClass {
+    serde_json::from_value(json!({
+        "id": "1",
+        "itemName": "“聆冬映雪”首届古琴音乐会",
+        "itemStatus": 26,
+        "validHour": 2.0,
+        "st": "2025-12-21 19:00:00",
+        "et": "2025-12-21 21:00:00",
+        "module": "m",
+        "module_dictText": "美",
+        "businessDeptId": "dept-qin",
+        "businessDeptName": "学生正则古琴协会",
+        "itemLable": "lab-show",
+        "lableNames": ["演出"],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn query_compiles_full_example() {
+    let q = Query::parse("美 dept:古琴 hours>=2 after:2025-12-01 status:applying -label:线上")
+        .expect("query should parse");
+    assert_eq!(q.terms.len(), 6);
+
+    let filter = q.compile(&index()).unwrap();
+    assert_eq!(filter.name, "美");
+    assert_eq!(filter.department.as_ref().unwrap().id, "dept-qin");
+    assert_eq!(filter.hours[0].op, CmpOp::Ge);
+    assert_eq!(filter.statuses, vec![Status::Applying]);
+    assert_eq!(filter.exclude_labels[0].id, "lab-online");
+    assert!(filter.strict_time);
+
+    // 名称中不含「美」，只验证其余条件
+    let filter = Query::parse("dept:古琴 module:美 hours>=2 after:2025-12-01 -label:线上")
+        .unwrap()
+        .compile(&index())
+        .unwrap();
+    assert!(filter.check(&concert(), false));
+
+    let filter = Query::parse("-label:演出")
+        .unwrap()
+        .compile(&index())
+        .unwrap();
+    assert!(!filter.check(&concert(), false));
+
+    let filter = Query::parse("hours>2")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap();
+    assert!(!filter.check(&concert(), false));
+}
+
+#[test]
+fn status_terms_select_the_lists_to_query() {
+    let lists = |q: &str| {
+        Query::parse(q)
+            .unwrap()
+            .compile(&TagIndex::default())
+            .unwrap()
+            .status_lists()
+    };
+    assert_eq!(lists("古琴"), (true, false));
+    assert_eq!(lists("status:applying"), (true, false));
+    assert_eq!(lists("status:finished"), (false, true));
+    // 只排除报名中：查已结束列表
+    assert_eq!(lists("-status:applying"), (false, true));
+    // 同时含报名中与其他状态：两个列表都查
+    assert_eq!(lists("status:applying status:finished"), (true, true));
+    assert_eq!(lists("-status:finished"), (true, true));
+    assert_eq!(lists("status:applying -status:applying"), (false, false));
+}
+
+#[test]
+fn query_quoted_phrase_and_negation() {
+    let filter = Query::parse(r#""古琴 音乐会" -讲座 on:"2025-12-21""#)
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap();
+    assert_eq!(filter.name, "古琴 音乐会");
+    assert_eq!(filter.exclude_keywords, vec!["讲座".to_string()]);
+    let period = filter.time_period.unwrap();
+    assert_eq!(
+        period.start.naive_local().to_string(),
+        "2025-12-21 00:00:00"
+    );
+    assert_eq!(period.end.naive_local().to_string(), "2025-12-21 23:59:59");
+}
+
+#[test]
+fn query_errors_carry_char_spans() {
+    let err = Query::parse("美 \"古琴").unwrap_err();
+    assert_eq!(err.span, Span { start: 2, end: 5 });
+
+    let err = Query::parse("美 foo:bar").unwrap_err();
+    assert_eq!(err.span, Span { start: 2, end: 5 });
+
+    let err = Query::parse("dept>古琴").unwrap_err();
+    assert_eq!(err.span, Span { start: 4, end: 5 });
+
+    let err = Query::parse("古琴 hours>=两")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap_err();
+    assert_eq!(err.span, Span { start: 10, end: 11 });
+
+    // 「艺术」同时匹配两个部门
+    let err = Query::parse("dept:艺术")
+        .unwrap()
+        .compile(&index())
+        .unwrap_err();
+    assert_eq!(err.span, Span { start: 5, end: 7 });
+    assert!(err.message.contains("艺术中心"));
+
+    let err = Query::parse("after:2025-12-02 before:2025-12-01")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap_err();
+    assert_eq!(err.span, Span { start: 0, end: 34 });
+}
+
+#[test]
+fn tag_index_resolves_pinyin_initials_and_paths() {
+    let index = index();
+
+    let hits = index.search(Some(TagKind::Department), "gqxh", 5);
+    assert_eq!(hits[0].id, "dept-qin");
+    assert_eq!(hits[0].path, vec!["中国科学技术大学", "学生正则古琴协会"]);
+
+    assert_eq!(index.module("M").unwrap().text, "美");
+    assert_eq!(index.label("lab-show").unwrap().name, "演出");
+    assert_eq!(index.department("艺术中心").unwrap().id, "dept-art");
+
+    let path = index.department_path("dept-art2").unwrap();
+    assert_eq!(path.len(), 2);
+    assert_eq!(path[0].id, "root");
+
+    // 拼音首字母同样可用于查询语言
+    let filter = Query::parse("dept:gqxh").unwrap().compile(&index).unwrap();
+    assert_eq!(filter.department.unwrap().id, "dept-qin");
+}
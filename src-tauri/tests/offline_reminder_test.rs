@@ -0,0 +1,73 @@
+use better_ustc_2_lib::rustustc::young::reminder::{plan, ReminderSettings};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::json;
+
+mod common;
+
+use common::at;
+
+fn upcoming(id: &str, st: &str) -> SecondClass {
+    common::activity(
+        id,
+        json!({
+            "itemName": "古琴音乐会",
+            "st": st,
+            "et": "2025-12-21 21:00:00",
+            "placeInfo": "东区水上报告厅",
+        }),
+    )
+}
+
+#[test]
+fn plan_skips_past_and_fired_reminders() {
+    let settings = ReminderSettings::default();
+    let list = vec![
+        upcoming("a1", "2025-12-21 19:00:00"),
+        upcoming("a2", "2025-12-01 19:00:00"),
+    ];
+
+    // 距开始不足 1 天：只剩 1 小时提醒；已开始的活动不提醒
+    let now = at("2025-12-21 08:00:00");
+    let reminders = plan(&list, &settings, &[], now);
+    assert_eq!(reminders.len(), 1);
+    let r = &reminders[0];
+    assert_eq!(r.activity_id, "a1");
+    assert_eq!(r.offset_minutes, 60);
+    assert_eq!(r.fire_at, at("2025-12-21 18:00:00"));
+    assert_eq!(
+        r.message(),
+        "「古琴音乐会」将于1 小时后（12-21 19:00）开始，地点：东区水上报告厅"
+    );
+
+    let now = at("2025-12-19 08:00:00");
+    let reminders = plan(&list, &settings, &[], now);
+    assert_eq!(reminders.len(), 2);
+    assert_eq!(reminders[0].offset_minutes, 24 * 60);
+    let fired = vec![reminders[0].key()];
+    assert_eq!(plan(&list, &settings, &fired, now).len(), 1);
+
+    // 时间变更后 key 不同，重新提醒
+    let moved = vec![upcoming("a1", "2025-12-21 18:30:00")];
+    assert_eq!(plan(&moved, &settings, &fired, now).len(), 2);
+
+    let disabled = ReminderSettings {
+        enabled: false,
+        ..settings
+    };
+    assert!(plan(&list, &disabled, &[], now).is_empty());
+}
+
+#[test]
+fn settings_validate_offsets() {
+    assert!(ReminderSettings::default().validate().is_ok());
+    let zero = ReminderSettings {
+        enabled: true,
+        offsets_minutes: vec![0],
+    };
+    assert!(zero.validate().is_err());
+    let too_many = ReminderSettings {
+        enabled: true,
+        offsets_minutes: vec![10, 20, 30, 40, 50, 60],
+    };
+    assert!(too_many.validate().is_err());
+}
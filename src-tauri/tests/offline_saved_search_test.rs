@@ -0,0 +1,60 @@
+use better_ustc_2_lib::rustustc::young::model::now;
+use better_ustc_2_lib::rustustc::young::saved_search::SavedSearch;
+use better_ustc_2_lib::rustustc::young::{Query, TagIndex};
+use serde_json::json;
+
+fn ids(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+fn search() -> SavedSearch {
+    let filter = Query::parse("古琴 hours>=2")
+        .unwrap()
+        .compile(&TagIndex::default())
+        .unwrap();
+    serde_json::from_value(json!({
+        "name": "古琴",
+        "query": "古琴 hours>=2",
+        "filter": filter,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn first_run_sets_baseline_then_reports_new_ids() {
+    let mut s = search();
+    assert!(s.notify);
+    assert!(s.record(&ids(&["a", "b"]), now()).is_empty());
+    assert_eq!(s.seen.len(), 2);
+
+    assert_eq!(s.record(&ids(&["a", "b", "c"]), now()), ids(&["c"]));
+    // 未读的不会重复报告
+    assert!(s.record(&ids(&["a", "b", "c"]), now()).is_empty());
+    assert_eq!(s.record(&ids(&["c", "d"]), now()), ids(&["d"]));
+    // 不再出现的 ID 被清理
+    assert_eq!(s.seen.len(), 0);
+    assert_eq!(s.unseen, ids(&["c", "d"]));
+
+    s.mark_all_seen();
+    assert!(s.unseen.is_empty());
+    assert!(s.seen.contains("c") && s.seen.contains("d"));
+    assert!(s.record(&ids(&["c", "d"]), now()).is_empty());
+}
+
+#[test]
+fn saved_search_round_trips_through_json() {
+    let mut s = search();
+    s.record(&ids(&["a"]), now());
+    s.record(&ids(&["a", "b"]), now());
+
+    let v = serde_json::to_value(&s).unwrap();
+    let back: SavedSearch = serde_json::from_value(v).unwrap();
+    // 时间按秒存储
+    assert_eq!(
+        back.last_run_at.map(|t| t.timestamp()),
+        s.last_run_at.map(|t| t.timestamp())
+    );
+    assert_eq!(back.unseen, ids(&["b"]));
+    assert_eq!(back.filter.name, "古琴");
+    assert_eq!(back.filter.hours.len(), 1);
+}
@@ -0,0 +1,82 @@
+use better_ustc_2_lib::rustustc::young::model::cst;
+use better_ustc_2_lib::rustustc::young::{SecondClass, Series, Status};
+use chrono::NaiveDateTime;
+use serde_json::json;
+
+fn session(id: &str, status: Status, st: &str, et: &str, registered: i32) -> SecondClass {
+    serde_json::from_value(json!({
+        "id": id,
+        "itemName": format!("系列讲座 第{}讲", id),
+        "itemStatus": status.code(),
+        "validHour": 1.5,
+        "applyNum": 10,
+        "peopleNum": 30,
+        "booleanRegistration": registered,
+        "itemCategory": "0",
+        "st": st,
+        "et": et,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn series_aggregates_children() {
+    let mut parent = session(
+        "p",
+        Status::Applying,
+        "2025-10-01 19:00:00",
+        "2025-12-31 21:00:00",
+        0,
+    );
+    parent.item_category = Some("1".into());
+
+    let children = vec![
+        session(
+            "3",
+            Status::Applying,
+            "2025-12-10 19:00:00",
+            "2025-12-10 21:00:00",
+            0,
+        ),
+        session(
+            "1",
+            Status::Finished,
+            "2025-10-10 19:00:00",
+            "2025-10-10 21:00:00",
+            0,
+        ),
+        session(
+            "2",
+            Status::ApplyEnded,
+            "2025-11-10 19:00:00",
+            "2025-11-10 21:00:00",
+            1,
+        ),
+    ];
+    // 第 1 讲只出现在已参与列表中
+    let participated = vec![session(
+        "1",
+        Status::Finished,
+        "2025-10-10 19:00:00",
+        "2025-10-10 21:00:00",
+        0,
+    )];
+    let now =
+        cst(NaiveDateTime::parse_from_str("2025-12-01 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap());
+
+    let series = Series::build(parent, children, &participated, now);
+    let ids: Vec<&str> = series
+        .sessions
+        .iter()
+        .map(|s| s.activity.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+    assert_eq!(series.total, 3);
+    assert_eq!(series.registered, 2);
+    assert_eq!(series.attended, 2);
+    assert_eq!(series.open, 1);
+    assert_eq!(series.total_hours, 4.5);
+    assert_eq!(series.registered_hours, 3.0);
+    assert_eq!(series.next_session.unwrap().id, "3");
+    assert_eq!(series.status_counts.len(), 3);
+}
@@ -0,0 +1,74 @@
+use better_ustc_2_lib::rustustc::error::AppError;
+use better_ustc_2_lib::rustustc::young::session::SessionState;
+
+fn active() -> SessionState {
+    SessionState::Active {
+        username: "PB21000001".into(),
+    }
+}
+
+#[test]
+fn only_active_or_refreshing_sessions_are_usable() {
+    let refreshing = SessionState::Refreshing {
+        username: "PB21000001".into(),
+    };
+    assert!(active().is_usable());
+    assert!(refreshing.is_usable());
+    assert!(!SessionState::LoggedOut.is_usable());
+
+    // 只有同一账号且处于 Active 时才复用
+    assert!(active().can_reuse("PB21000001"));
+    assert!(!active().can_reuse("PB21000002"));
+    assert!(!refreshing.can_reuse("PB21000001"));
+
+    // 命令区分未登录与会话失效
+    let expired = active().expire("Token失效").unwrap();
+    assert_eq!(
+        expired.unavailable_error(),
+        AppError::SessionExpired("Token失效".into())
+    );
+    assert_eq!(
+        SessionState::LoggedOut.unavailable_error(),
+        AppError::AuthRequired
+    );
+}
+
+#[test]
+fn token_errors_expire_only_usable_sessions() {
+    assert_eq!(
+        active().expire("Token失效"),
+        Some(SessionState::Expired {
+            username: "PB21000001".into(),
+            reason: "Token失效".into(),
+        })
+    );
+    // 已登出或已失效时不再变化
+    assert_eq!(SessionState::LoggedOut.expire("Token失效"), None);
+    let expired = active().expire("a").unwrap();
+    assert_eq!(expired.expire("b"), None);
+}
+
+#[test]
+fn refresh_outcome_decides_the_next_state() {
+    let user = "PB21000001";
+    assert_eq!(
+        SessionState::after_refresh(active(), user, Ok(())),
+        active()
+    );
+
+    // 网络错误保留刷新前的状态，旧会话继续使用
+    let offline = AppError::Network("timeout".into());
+    assert_eq!(
+        SessionState::after_refresh(active(), user, Err(&offline)),
+        active()
+    );
+
+    let cas_gone = AppError::SessionExpired("CAS Session expired. Please login again.".into());
+    assert_eq!(
+        SessionState::after_refresh(active(), user, Err(&cas_gone)),
+        SessionState::Expired {
+            username: user.into(),
+            reason: "CAS Session expired. Please login again.".into(),
+        }
+    );
+}
@@ -0,0 +1,87 @@
+use better_ustc_2_lib::rustustc::young::rate_limit::{priority, RateLimiter, TokenBucket};
+use better_ustc_2_lib::rustustc::young::service::skew_from_date;
+use better_ustc_2_lib::rustustc::young::sniper::{should_retry, JobState, SniperJob, MAX_ATTEMPTS};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::json;
+use std::time::{Duration as StdDuration, Instant};
+
+mod common;
+
+use common::at;
+
+fn opening(registered: i32) -> SecondClass {
+    common::activity(
+        "s1",
+        json!({
+            "itemName": "冬至音乐会",
+            "booleanRegistration": registered,
+            "applySt": "2025-12-01 12:00:00",
+            "applyEt": "2025-12-10 12:00:00",
+        }),
+    )
+}
+
+#[test]
+fn job_fires_at_server_opening_time() {
+    let now = at("2025-12-01 11:00:00");
+    let job = SniperJob::new(&opening(0), None, false, now).unwrap();
+    assert_eq!(job.state, JobState::Scheduled);
+    assert!(job.is_active());
+    assert_eq!(job.fire_at(None), at("2025-12-01 12:00:00"));
+    // 服务器快 1.5 秒：本地需提前 1.5 秒
+    assert_eq!(
+        job.fire_at(Some(1500)),
+        at("2025-12-01 11:59:58") + chrono::Duration::milliseconds(500)
+    );
+    assert_eq!(job.prewarm_at(None), at("2025-12-01 11:59:00"));
+    assert!(!job.missed(at("2025-12-01 12:05:00")));
+    assert!(job.missed(at("2025-12-01 12:30:00")));
+
+    assert!(SniperJob::new(&opening(1), None, false, now).is_err());
+    assert!(SniperJob::new(&opening(0), None, false, at("2025-12-01 12:00:01")).is_err());
+
+    let v = serde_json::to_value(JobState::Failed {
+        message: "x".into(),
+    })
+    .unwrap();
+    assert_eq!(v, json!({ "state": "failed", "message": "x" }));
+}
+
+#[test]
+fn retry_policy_and_clock_skew() {
+    assert!(should_retry("API Error: \"报名尚未开始\"", 1));
+    assert!(!should_retry("API Error: \"报名人数已满\"", 1));
+    assert!(!should_retry("API Error: \"您已报名该活动\"", 1));
+    assert!(!should_retry("error sending request", MAX_ATTEMPTS));
+
+    // 服务器时间 12:00:00（GMT 04:00:00）+ 0.5 秒，请求往返中点为本地 12:00:02
+    let local = at("2025-12-01 12:00:00").timestamp_millis();
+    let skew = skew_from_date(
+        "Mon, 01 Dec 2025 04:00:00 GMT",
+        local + 1_900,
+        local + 2_100,
+    );
+    assert_eq!(skew, Some(500 - 2_000));
+    assert_eq!(skew_from_date("not a date", 0, 0), None);
+}
+
+#[tokio::test]
+async fn priority_apply_is_not_delayed_by_drained_bucket() {
+    // 每 10 秒补一个令牌：令牌用完后普通请求要等 10 秒
+    let limiter = RateLimiter::new(1, 0.1);
+    limiter.acquire().await;
+    let window = StdDuration::from_millis(200);
+    assert!(tokio::time::timeout(window, priority(limiter.acquire()))
+        .await
+        .is_ok());
+    assert!(tokio::time::timeout(window, limiter.acquire())
+        .await
+        .is_err());
+
+    // 插队的请求仍计入令牌桶，之后的普通请求多等
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(1, 4.0, start);
+    bucket.take(start);
+    bucket.take(start);
+    assert_eq!(bucket.reserve(start), StdDuration::from_millis(500));
+}
@@ -0,0 +1,138 @@
+use better_ustc_2_lib::rustustc::error::AppError;
+use better_ustc_2_lib::rustustc::young::model::User;
+use better_ustc_2_lib::rustustc::young::{SecondClass, Team, TeamLimits};
+use serde_json::{json, Value};
+
+mod common;
+
+fn team_activity(team_size: Value, min: Value, team_num: Value, applied: Value) -> SecondClass {
+    common::activity(
+        "t1",
+        json!({
+            "itemName": "程序设计竞赛",
+            "teamSize": team_size,
+            "itemLimitNum": min,
+            "teamNum": team_num,
+            "applyTeamNum": applied,
+        }),
+    )
+}
+
+fn ids(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn limits_validate_team_creation() {
+    assert!(TeamLimits::from_activity(&team_activity(
+        Value::Null,
+        json!(2),
+        Value::Null,
+        json!(0)
+    ))
+    .is_none());
+    let err = TeamLimits::require(&team_activity(Value::Null, json!(2), Value::Null, json!(0)))
+        .map_err(AppError::from)
+        .unwrap_err();
+    assert_eq!(err.code(), "NOT_A_TEAM_ACTIVITY");
+
+    let limits =
+        TeamLimits::from_activity(&team_activity(json!("3"), json!(2), json!(10), json!(4)))
+            .unwrap();
+    assert_eq!((limits.min_size, limits.max_size), (2, 3));
+    assert_eq!(limits.max_teams, Some(10));
+    assert!(!limits.teams_full());
+
+    assert!(limits.check_create("PB001", &ids(&["PB002"])).is_ok());
+    assert!(limits
+        .check_create("PB001", &ids(&["PB002", "PB003"]))
+        .is_ok());
+    // 人数不足 / 超员 / 重复 / 包含队长
+    assert!(limits.check_create("PB001", &[]).is_err());
+    assert!(limits
+        .check_create("PB001", &ids(&["PB002", "PB003", "PB004"]))
+        .is_err());
+    assert!(limits
+        .check_create("PB001", &ids(&["PB002", "PB002"]))
+        .is_err());
+    assert!(limits
+        .check_create("PB001", &ids(&["PB001", "PB002"]))
+        .is_err());
+
+    let full =
+        TeamLimits::from_activity(&team_activity(json!(3), Value::Null, json!(5), json!("5")))
+            .unwrap();
+    assert_eq!(full.min_size, 1);
+    assert!(full.teams_full());
+    assert!(full.check_create("PB001", &ids(&["PB002"])).is_err());
+}
+
+#[test]
+fn join_checks_capacity_and_membership() {
+    let limits =
+        TeamLimits::from_activity(&team_activity(json!(2), json!(1), Value::Null, json!(0)))
+            .unwrap();
+    let team: Team = serde_json::from_value(json!({
+        "id": "team1",
+        "teamName": "Rustaceans",
+        "itemId": "t1",
+        "teamCode": "ABCD",
+        "leaderId": "PB001",
+        "members": [{ "userId": "PB001", "realname": "张三" }],
+    }))
+    .unwrap();
+    assert!(team.is_leader("PB001"));
+    assert!(limits.check_join(&team, "PB001").is_err());
+    assert!(limits.check_join(&team, "PB002").is_ok());
+
+    let mut full = team.clone();
+    full.members
+        .push(serde_json::from_value(json!({ "id": "PB002", "name": "李四" })).unwrap());
+    assert!(limits.check_join(&full, "PB003").is_err());
+}
+
+#[test]
+fn leader_is_matched_by_user_id_not_student_number() {
+    // 成员记录带关系记录自身的 id、用户 ID 与学号
+    let team: Team = serde_json::from_value(json!({
+        "id": "1733000000000000001",
+        "teamName": "Rustaceans",
+        "itemId": "t1",
+        "teamCode": "ABCD",
+        "leaderId": "1589000000000000001",
+        "members": [
+            { "id": "1733000000000000011", "userId": "1589000000000000001",
+              "username": "PB21000001", "realname": "张三" },
+            { "id": "1733000000000000012", "userId": "1589000000000000002",
+              "username": "PB21000002", "realname": "李四" },
+        ],
+    }))
+    .unwrap();
+    let me: User = serde_json::from_value(json!({
+        "id": "1589000000000000001",
+        "realname": "张三",
+        "sex_dictText": "男",
+        "grade": "2021",
+        "classes": "少年班",
+        "scientificqiValue": 0,
+    }))
+    .unwrap();
+
+    assert!(team.is_leader(&me.id));
+    assert!(!team.is_leader("PB21000001"));
+    assert_eq!(team.members[1].id, "1589000000000000002");
+    assert_eq!(team.members[1].username.as_deref(), Some("PB21000002"));
+
+    let limits =
+        TeamLimits::from_activity(&team_activity(json!(3), json!(1), Value::Null, json!(0)))
+            .unwrap();
+    assert!(limits.check_join(&team, &me.id).is_err());
+    assert!(limits.check_join(&team, "1589000000000000003").is_ok());
+
+    // 没有 leaderId 时按 isLeader 判断
+    let mut unflagged = team.clone();
+    unflagged.leader_id = None;
+    unflagged.members[1].is_leader = true;
+    assert!(unflagged.is_leader("1589000000000000002"));
+    assert!(!unflagged.is_leader(&me.id));
+}
@@ -0,0 +1,126 @@
+use better_ustc_2_lib::rustustc::young::model::{cst, TimePeriod, TimeSlotSet, SHANGHAI};
+use chrono::{Duration, NaiveDateTime, TimeZone};
+use proptest::prelude::*;
+
+fn at(s: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+}
+
+/// 2025-12-01 起 10 天内、以分钟为粒度的随机区间
+fn period() -> impl Strategy<Value = TimePeriod> {
+    (0i64..14_400, 0i64..600).prop_map(|(offset, len)| {
+        let base = cst(at("2025-12-01 00:00:00"));
+        let start = base + Duration::minutes(offset);
+        TimePeriod::new(start, start + Duration::minutes(len)).unwrap()
+    })
+}
+
+#[test]
+fn time_period_is_anchored_to_shanghai() {
+    let p = TimePeriod::parse_period("2025-12-21 19:00:00", Some("2025-12-21 21:00:00")).unwrap();
+    // 19:00 北京时间 = 11:00 UTC，与设备时区无关
+    assert_eq!(p.start.naive_utc(), at("2025-12-21 11:00:00"));
+    assert_eq!(p.duration(), Duration::hours(2));
+
+    let json = serde_json::to_value(p).unwrap();
+    assert_eq!(json["start"], "2025-12-21T19:00:00+08:00");
+    let back: TimePeriod = serde_json::from_value(json).unwrap();
+    assert_eq!(back, p);
+
+    // 无时区的旧格式按北京时间解释
+    let legacy: TimePeriod = serde_json::from_value(
+        serde_json::json!({"start": "2025-12-21T19:00:00", "end": "2025-12-21T21:00:00"}),
+    )
+    .unwrap();
+    assert_eq!(legacy, p);
+    assert_eq!(
+        legacy.start,
+        SHANGHAI.with_ymd_and_hms(2025, 12, 21, 19, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn time_slot_set_free_gaps() {
+    let p = |a: &str, b: &str| TimePeriod::from_naive(at(a), at(b)).unwrap();
+    let busy = TimeSlotSet::from_periods(vec![
+        p("2025-12-01 14:00:00", "2025-12-01 15:00:00"),
+        p("2025-12-01 09:00:00", "2025-12-01 10:00:00"),
+        p("2025-12-01 09:30:00", "2025-12-01 11:00:00"),
+    ]);
+    assert_eq!(busy.slots().len(), 2);
+
+    let day = p("2025-12-01 08:00:00", "2025-12-01 18:00:00");
+    let gaps = busy.free_gaps(&day, Duration::minutes(90));
+    assert_eq!(
+        gaps,
+        vec![
+            p("2025-12-01 11:00:00", "2025-12-01 14:00:00"),
+            p("2025-12-01 15:00:00", "2025-12-01 18:00:00"),
+        ]
+    );
+}
+
+proptest! {
+    #[test]
+    fn intersect_matches_overlap(a in period(), b in period()) {
+        let i = a.intersect(&b);
+        prop_assert_eq!(i.is_some(), a.is_overlap(&b));
+        prop_assert_eq!(i, b.intersect(&a));
+        if let Some(i) = i {
+            prop_assert!(a.is_contain(&i) && b.is_contain(&i));
+        }
+    }
+
+    #[test]
+    fn union_covers_both(a in period(), b in period()) {
+        match a.union(&b) {
+            Some(u) => {
+                prop_assert!(u.is_contain(&a) && u.is_contain(&b));
+                prop_assert_eq!(u.duration(), a.duration() + b.duration() - a.intersect(&b).unwrap().duration());
+            }
+            None => prop_assert!(!a.is_overlap(&b)),
+        }
+    }
+
+    #[test]
+    fn subtract_partitions_duration(a in period(), b in period()) {
+        let rest = a.subtract(&b);
+        prop_assert!(rest.len() <= 2);
+        let kept = rest.iter().fold(Duration::zero(), |acc, r| acc + r.duration());
+        let cut = a.intersect(&b).map_or(Duration::zero(), |i| i.duration());
+        prop_assert_eq!(kept + cut, a.duration());
+        for r in &rest {
+            prop_assert!(a.is_contain(r));
+            // 剩余部分与 b 至多首尾相接
+            prop_assert!(r.intersect(&b).is_none_or(|i| i.duration() == Duration::zero()));
+        }
+    }
+
+    #[test]
+    fn merge_all_is_sorted_and_disjoint(ps in prop::collection::vec(period(), 0..20)) {
+        let merged = TimePeriod::merge_all(ps.clone());
+        for w in merged.windows(2) {
+            prop_assert!(w[0].end < w[1].start);
+        }
+        for p in &ps {
+            prop_assert!(merged.iter().any(|m| m.is_contain(p)));
+        }
+        prop_assert_eq!(TimePeriod::merge_all(merged.clone()), merged);
+    }
+
+    #[test]
+    fn free_gaps_complement_busy(ps in prop::collection::vec(period(), 0..10), window in period()) {
+        let busy = TimeSlotSet::from_periods(ps);
+        let gaps = busy.free_gaps(&window, Duration::zero());
+        let busy_in_window = busy
+            .conflicts(&window)
+            .iter()
+            .fold(Duration::zero(), |acc, c| acc + c.duration());
+        let free = gaps.iter().fold(Duration::zero(), |acc, g| acc + g.duration());
+        prop_assert_eq!(free + busy_in_window, window.duration());
+        for g in &gaps {
+            prop_assert!(window.is_contain(g));
+            prop_assert!(busy.conflicts(g).iter().all(|c| c.duration() == Duration::zero()));
+        }
+    }
+}
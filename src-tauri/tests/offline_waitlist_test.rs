@@ -0,0 +1,100 @@
+use better_ustc_2_lib::rustustc::error::AppError;
+use better_ustc_2_lib::rustustc::young::rate_limit::TokenBucket;
+use better_ustc_2_lib::rustustc::young::waitlist::{
+    ends_waiting, has_free_seat, next_interval, WaitEntry, WaitState, MAX_INTERVAL_SECS,
+    MIN_INTERVAL_SECS,
+};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use chrono::Duration;
+use serde_json::json;
+use std::time::{Duration as StdDuration, Instant};
+
+mod common;
+
+use common::at;
+
+fn seats(apply_num: i32, limit: i32) -> SecondClass {
+    common::activity(
+        "w1",
+        json!({
+            "itemName": "冬至音乐会",
+            "booleanRegistration": 0,
+            "applyNum": apply_num,
+            "peopleNum": limit,
+            "applySt": "2025-12-01 12:00:00",
+            "applyEt": "2025-12-10 12:00:00",
+        }),
+    )
+}
+
+#[test]
+fn waitlist_polls_adaptively_until_deadline() {
+    assert!(!has_free_seat(&seats(250, 250)));
+    assert!(has_free_seat(&seats(249, 250)));
+
+    let deadline = at("2025-12-10 12:00:00");
+    let secs = |now, changed| next_interval(deadline, at(now), changed).num_seconds();
+    assert_eq!(secs("2025-12-05 12:00:00", false), MAX_INTERVAL_SECS);
+    assert_eq!(secs("2025-12-10 00:00:00", false), 120);
+    assert_eq!(secs("2025-12-10 11:30:00", false), MIN_INTERVAL_SECS);
+    assert_eq!(secs("2025-12-05 12:00:00", true), MIN_INTERVAL_SECS);
+
+    let now = at("2025-12-05 12:00:00");
+    let mut entry = WaitEntry::new(&seats(250, 250), None, false, now).unwrap();
+    assert!(entry.is_due(now));
+    entry.observe(&seats(250, 250), now);
+    assert_eq!(
+        entry.next_check_at,
+        now + Duration::seconds(MAX_INTERVAL_SECS)
+    );
+    assert!(!entry.is_due(now + Duration::seconds(60)));
+    // 报名人数有变动，下次立即缩短间隔
+    entry.observe(&seats(249, 250), now);
+    assert_eq!(
+        entry.next_check_at,
+        now + Duration::seconds(MIN_INTERVAL_SECS)
+    );
+
+    entry.observe(&seats(250, 250), deadline);
+    assert_eq!(entry.state, WaitState::Expired);
+    assert!(!entry.is_due(deadline));
+
+    assert!(WaitEntry::new(&seats(250, 250), None, false, deadline).is_err());
+}
+
+#[test]
+fn only_business_rejections_end_waiting() {
+    assert!(!ends_waiting(&AppError::ActivityFull("人数已满".into())));
+    assert!(!ends_waiting(&AppError::Network("timeout".into())));
+    assert!(!ends_waiting(&AppError::SessionExpired("Token失效".into())));
+    assert!(ends_waiting(&AppError::Youth("不符合报名条件".into())));
+    assert!(ends_waiting(&AppError::TimeConflict("时间冲突".into())));
+
+    // 查询失败时推迟下次检查，状态不变
+    let now = at("2025-12-05 12:00:00");
+    let mut entry = WaitEntry::new(&seats(250, 250), None, false, now).unwrap();
+    entry.defer(now);
+    assert!(entry.is_waiting());
+    assert_eq!(
+        entry.next_check_at,
+        now + Duration::seconds(MAX_INTERVAL_SECS)
+    );
+    entry.defer(at("2025-12-10 12:00:00"));
+    assert_eq!(entry.state, WaitState::Expired);
+}
+
+#[test]
+fn token_bucket_allows_burst_then_spaces_requests() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(2, 4.0, start);
+    assert_eq!(bucket.reserve(start), StdDuration::ZERO);
+    assert_eq!(bucket.reserve(start), StdDuration::ZERO);
+    // 令牌用完后按预支顺序排队
+    assert_eq!(bucket.reserve(start), StdDuration::from_millis(250));
+    assert_eq!(bucket.reserve(start), StdDuration::from_millis(500));
+    // 1 秒后补回 4 个令牌，抵消预支的 2 个
+    let later = start + StdDuration::from_secs(1);
+    assert_eq!(bucket.reserve(later), StdDuration::ZERO);
+    assert_eq!(bucket.reserve(later), StdDuration::ZERO);
+    assert_eq!(bucket.reserve(later), StdDuration::from_millis(250));
+}
@@ -0,0 +1,83 @@
+use better_ustc_2_lib::rustustc::young::model::now;
+use better_ustc_2_lib::rustustc::young::watch::{diff, Change, ChangeEvent, Snapshot};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::{json, Value};
+
+fn detail(st: &str, place: &str, status: i32, del_flag: Value) -> SecondClass {
+    serde_json::from_value(json!({
+        "id": "a1",
+        "itemName": "古琴音乐会",
+        "itemStatus": status,
+        "st": st,
+        "et": "2025-12-21 21:00:00",
+        "placeInfo": place,
+        "delFlag": del_flag,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn diff_reports_time_place_and_status() {
+    let old = Snapshot::of(&detail(
+        "2025-12-21 19:00:00",
+        "东区水上报告厅",
+        26,
+        json!(0),
+    ));
+    assert!(diff(&old, &old.clone()).is_empty());
+
+    let new = Snapshot::of(&detail("2025-12-21 18:30:00", "西区活动中心", 28, json!(0)));
+    let changes = diff(&old, &new);
+    assert_eq!(changes.len(), 3);
+    assert_eq!(
+        changes[0],
+        Change::TimeChanged {
+            old_start: Some("2025-12-21 19:00:00".into()),
+            old_end: Some("2025-12-21 21:00:00".into()),
+            new_start: Some("2025-12-21 18:30:00".into()),
+            new_end: Some("2025-12-21 21:00:00".into()),
+        }
+    );
+    assert!(
+        matches!(&changes[1], Change::PlaceChanged { new: Some(p), .. } if p == "西区活动中心")
+    );
+    assert_eq!(changes[2], Change::StatusChanged { old: 26, new: 28 });
+
+    let event = ChangeEvent {
+        activity_id: new.id.clone(),
+        activity_name: new.name.clone(),
+        detected_at: now(),
+        change: changes[1].clone(),
+    };
+    assert_eq!(event.message(), "「古琴音乐会」地点变更为 西区活动中心");
+    let v = serde_json::to_value(&event).unwrap();
+    assert_eq!(v["change"]["kind"], "place_changed");
+}
+
+#[test]
+fn cancellation_reported_once() {
+    let old = Snapshot::of(&detail(
+        "2025-12-21 19:00:00",
+        "东区水上报告厅",
+        26,
+        json!(0),
+    ));
+    let cancelled = Snapshot::of(&detail(
+        "2025-12-22 19:00:00",
+        "东区水上报告厅",
+        26,
+        json!("1"),
+    ));
+    assert!(cancelled.cancelled);
+    // 取消后不再报告时间等其他变化
+    assert_eq!(diff(&old, &cancelled), vec![Change::Cancelled]);
+    assert!(diff(&cancelled, &cancelled.clone()).is_empty());
+
+    let abnormal = Snapshot::of(&detail(
+        "2025-12-21 19:00:00",
+        "东区水上报告厅",
+        -3,
+        Value::Null,
+    ));
+    assert_eq!(diff(&old, &abnormal), vec![Change::Cancelled]);
+}
@@ -0,0 +1,71 @@
+use better_ustc_2_lib::rustustc::error::AppError;
+use better_ustc_2_lib::rustustc::young::work::{
+    list_unavailable, validate_files, window, WorkStatus, MAX_WORK_FILE_SIZE,
+};
+use better_ustc_2_lib::rustustc::young::SecondClass;
+use serde_json::{json, Value};
+
+mod common;
+
+use common::at;
+
+fn contest(can_submit: Value, work_status: Value) -> SecondClass {
+    common::activity(
+        "w1",
+        json!({
+            "itemName": "摄影大赛",
+            "itemStatus": 30,
+            "booleanRegistration": 1,
+            "canSubmitWork": can_submit,
+            "workStatus": work_status,
+            "attaEndTime": "2025-12-31 23:59:59",
+        }),
+    )
+}
+
+#[test]
+fn submission_window_enforces_deadline() {
+    let before = at("2025-12-31 12:00:00");
+    let after = at("2026-01-01 00:00:00");
+
+    let sc = contest(json!("1"), Value::Null);
+    let w = window(&sc, before);
+    assert!(w.open);
+    assert_eq!(w.deadline, Some(at("2025-12-31 23:59:59")));
+
+    let w = window(&sc, after);
+    assert!(!w.open);
+    assert_eq!(w.reason.as_deref(), Some("已过提交截止时间"));
+
+    assert!(!window(&contest(json!(0), Value::Null), before).open);
+    // 被驳回可重新提交，已通过则关闭
+    assert!(window(&contest(json!(true), json!(2)), before).open);
+    assert!(!window(&contest(json!(true), json!("1")), before).open);
+
+    assert_eq!(WorkStatus::from_raw(None), WorkStatus::NotSubmitted);
+    assert_eq!(
+        WorkStatus::from_raw(Some(&json!("0"))),
+        WorkStatus::Reviewing
+    );
+    assert_eq!(WorkStatus::from_raw(Some(&json!(2))), WorkStatus::Rejected);
+}
+
+#[test]
+fn work_file_limits() {
+    let file = |size: usize| ("a.pdf".to_string(), vec![0u8; size]);
+    assert!(validate_files(&[]).is_err());
+    assert!(validate_files(&[file(1)]).is_ok());
+    assert!(validate_files(&vec![file(1); 6]).is_err());
+    assert!(validate_files(&[file(MAX_WORK_FILE_SIZE + 1)]).is_err());
+}
+
+#[test]
+fn file_list_falls_back_only_when_endpoint_is_unavailable() {
+    assert!(list_unavailable(&AppError::Youth("".into())));
+    assert!(list_unavailable(&AppError::Parse("expected value".into())));
+    assert!(!list_unavailable(&AppError::Network("timeout".into())));
+    assert!(!list_unavailable(&AppError::SessionExpired(
+        "Token失效".into()
+    )));
+    assert!(!list_unavailable(&AppError::AuthRequired));
+}
@@ -33,7 +33,7 @@ async fn get_activity_detail(
     };
 
     // 2. 调用 update 从服务器获取最新详情
-    sc.update(&service).await.expect("Failed to update activity detail");
+    sc.update(service).await.expect("Failed to update activity detail");
 
     // 3. 返回完整的对象
     Ok(sc)
@@ -157,7 +157,7 @@ async fn test_young_full_flow() {
     println!("Activity Detail: {}", json_detail);
 
     if !detail.applied() {
-        let result = detail.apply(&young, true, false, None).await.expect("Failed to apply for activity");
+        let result = detail.apply(&young, true, false, None, |_| None).await.expect("Failed to apply for activity");
         println!("Re-applied for activity, result: {}", result);
     } else {
         let result = detail.cancel_apply(&young).await.expect("Failed to cancel activity");
@@ -2,6 +2,7 @@ import { defineStore } from 'pinia'
 import { invoke } from '@tauri-apps/api/core'
 import { format } from 'date-fns'
 import { useLogStore } from './logs'
+import { errorMessage } from '../utils/error'
 
 export interface Activity {
   id: string
@@ -95,7 +96,7 @@ export const useActivityStore = defineStore('activity', {
         this.all = res
         useLogStore().add(`Fetched ${res.length} activities`)
       } catch (e: any) {
-        this.error = e?.toString?.() || '获取活动失败'
+        this.error = errorMessage(e) || '获取活动失败'
         useLogStore().add(`Fetch all failed: ${this.error}`)
       } finally {
         this.loadingAll = false
@@ -110,7 +111,7 @@ export const useActivityStore = defineStore('activity', {
         this.recommended = res
         useLogStore().add(`Fetched ${res.length} recommended activities`)
       } catch (e: any) {
-        this.error = e?.toString?.() || '获取推荐失败'
+        this.error = errorMessage(e) || '获取推荐失败'
         useLogStore().add(`Fetch recommended failed: ${this.error}`)
       } finally {
         this.loadingRec = false
@@ -127,7 +128,7 @@ export const useActivityStore = defineStore('activity', {
         this.participated = part as any
         useLogStore().add(`Fetched ${reg.length} registered, ${part.length} participated`)
       } catch (e: any) {
-        this.error = e?.toString?.() || '获取我的活动失败'
+        this.error = errorMessage(e) || '获取我的活动失败'
         useLogStore().add(`Fetch mine failed: ${this.error}`)
       } finally {
         this.loadingMine = false
@@ -151,7 +152,7 @@ export const useActivityStore = defineStore('activity', {
         this.detail.set(id, res)
         return res
       } catch (e: any) {
-        this.error = e?.toString?.() || '获取详情失败'
+        this.error = errorMessage(e) || '获取详情失败'
         useLogStore().add(`Fetch detail failed: ${this.error}`)
         throw e
       }
@@ -171,7 +172,7 @@ export const useActivityStore = defineStore('activity', {
         this.detail.set(id, res)
         return res
       } catch (e: any) {
-        this.error = e?.toString?.() || '刷新详情失败'
+        this.error = errorMessage(e) || '刷新详情失败'
         useLogStore().add(`Refresh detail failed: ${this.error}`)
         throw e
       }
@@ -183,7 +184,7 @@ export const useActivityStore = defineStore('activity', {
         useLogStore().add(`Apply raw result: ${JSON.stringify(res)}, type: ${typeof res}, === true: ${res === true}`)
         return res as boolean
       } catch (e: any) {
-        const errStr = e?.toString?.() || ''
+        const errStr = errorMessage(e)
         useLogStore().add(`Apply failed: ${errStr}`)
         // 返回错误字符串而不是throw，让调用方判断是否是时间冲突
         return errStr
@@ -196,7 +197,7 @@ export const useActivityStore = defineStore('activity', {
         useLogStore().add(`Cancel result: ${res}`)
         return res
       } catch (e: any) {
-        useLogStore().add(`Cancel failed: ${e}`)
+        useLogStore().add(`Cancel failed: ${errorMessage(e)}`)
         throw e
       }
     },
@@ -1,6 +1,7 @@
 import { defineStore } from 'pinia'
 import { invoke } from '@tauri-apps/api/core'
 import { useLogStore } from './logs'
+import { errorMessage } from '../utils/error'
 
 export interface UserInfo {
   id: string
@@ -12,11 +13,23 @@ export interface UserInfo {
   avatar?: string | null
 }
 
+export type SessionState =
+  | { state: 'logged_out' }
+  | { state: 'logging_in' | 'active' | 'refreshing'; username: string }
+  | { state: 'expired'; username: string; reason: string }
+  | { state: 'error'; message: string }
+
 interface LoginState {
   logged_in: boolean
   has_stored_creds: boolean
   username?: string | null
   user?: UserInfo | null
+  session?: SessionState
+}
+
+export interface LogoutReport {
+  ok: boolean
+  steps: { step: string; ok: boolean; error?: string | null }[]
 }
 
 export const useUserStore = defineStore('user', {
@@ -38,12 +51,37 @@ export const useUserStore = defineStore('user', {
         this.status = res
         useLogStore().add(`Fetch status: logged_in=${res.logged_in}, user=${res.user?.name}`)
       } catch (e: any) {
-        this.error = e?.toString?.() || '获取状态失败'
+        this.error = errorMessage(e) || '获取状态失败'
         useLogStore().add(`Fetch status failed: ${this.error}`)
       } finally {
         this.loading = false
       }
     },
+    // 启动时调用：用内存会话或存储的凭据登录
+    async autoLogin() {
+      this.loading = true
+      this.error = ''
+      try {
+        const res = (await invoke('auto_login')) as LoginState
+        this.status = res
+        useLogStore().add(`Auto login: logged_in=${res.logged_in}, user=${res.user?.name}`)
+      } catch (e: any) {
+        this.error = errorMessage(e) || '自动登录失败'
+        useLogStore().add(`Auto login failed: ${this.error}`)
+      } finally {
+        this.loading = false
+      }
+    },
+    // 应用回到前台时调用：检查会话，必要时刷新或重新登录（状态变化由事件同步）
+    async checkSession() {
+      if (!this.status?.session || this.status.session.state === 'logged_out') return
+      try {
+        const res = (await invoke('check_session')) as { action: string }
+        if (res.action !== 'healthy') useLogStore().add(`Check session: ${res.action}`)
+      } catch (e: any) {
+        useLogStore().add(`Check session failed: ${errorMessage(e)}`)
+      }
+    },
     async login(username: string, password: string, save: boolean) {
       this.loading = true
       this.error = ''
@@ -54,22 +92,26 @@ export const useUserStore = defineStore('user', {
         useLogStore().add(`Login success: ${user.name}`)
         return true
       } catch (e: any) {
-        this.error = e?.toString?.() || '登录失败'
+        this.error = errorMessage(e) || '登录失败'
         useLogStore().add(`Login failed: ${this.error}`)
         return false
       } finally {
         this.loading = false
       }
     },
-    async logout() {
-      useLogStore().add('Logging out')
+    // forgetAccount 为真时同时删除保存的用户名与报名信息等本地数据
+    async logout(forgetAccount = false) {
+      useLogStore().add(`Logging out (forget_account=${forgetAccount})`)
       try {
-        await invoke('logout')
+        const report = (await invoke('logout', { forget_account: forgetAccount })) as LogoutReport
+        report.steps
+          .filter((s) => !s.ok)
+          .forEach((s) => useLogStore().add(`Logout step ${s.step} failed: ${s.error}`))
         // Refresh status to get the saved username (if any)
         await this.fetchStatus()
       } catch (e) {
         console.error(e)
-        useLogStore().add(`Logout error: ${e}`)
+        useLogStore().add(`Logout error: ${errorMessage(e)}`)
         this.status = { logged_in: false, has_stored_creds: false, username: null, user: null }
       }
     },
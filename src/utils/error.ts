@@ -0,0 +1,17 @@
+// 后端命令的错误为 `{ code, message, retryable, ... }`，错误码见 docs/backend.md
+export interface AppError {
+  code: string
+  message: string
+  retryable: boolean
+  [key: string]: unknown
+}
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === 'object' && e !== null && 'code' in e && 'message' in e
+}
+
+export function errorMessage(e: unknown): string {
+  if (isAppError(e)) return e.message
+  if (e instanceof Error) return e.message
+  return e == null ? '' : String(e)
+}